@@ -0,0 +1,318 @@
+// Terminal client: plays the story on a plain console, no browser or server needed
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+use l3_story_game::models::{ChoiceData, GameState, Locale, StoryData, StoryGraph, TextSpeed, TimeGem};
+use l3_story_game::services::{PathNavigator, StoryLoader};
+use l3_story_game::utils::{take_option, AsciiTreeGenerator, I18n, Shortcut, TextStreamer, GEM_KEY};
+use std::env;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+const DEFAULT_STORY_FILE: &str = "../docs/FM_STORY.toml";
+const PANEL_GAP: usize = 4;
+
+const USAGE: &str = "Usage: l3play [--file FM_STORY.toml] [--lang zh-CN|en] [--speed slow|normal|fast|instant]
+
+Keys: the option keys (R, B, ...) choose, Space shows the whole text,
++ picks up a time gem, Q or Esc quits.
+
+The story file defaults to $L3_STORY_FILE, then ../docs/FM_STORY.toml.";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| matches!(arg.as_str(), "help" | "--help" | "-h")) {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let file = match take_option(&mut args, "--file") {
+        Ok(Some(file)) => file,
+        Ok(None) => env::var("L3_STORY_FILE").unwrap_or_else(|_| DEFAULT_STORY_FILE.to_string()),
+        Err(message) => return usage_error(&message),
+    };
+    let locale = match take_option(&mut args, "--lang") {
+        Ok(Some(code)) => match Locale::from_code(&code) {
+            Some(locale) => locale,
+            None => return usage_error(&format!("unknown language `{}`", code)),
+        },
+        Ok(None) => Locale::default(),
+        Err(message) => return usage_error(&message),
+    };
+    let speed = match take_option(&mut args, "--speed") {
+        Ok(Some(key)) => match TextSpeed::from_key(&key) {
+            Some(speed) => speed,
+            None => return usage_error(&format!("unknown speed `{}`", key)),
+        },
+        Ok(None) => TextSpeed::default(),
+        Err(message) => return usage_error(&message),
+    };
+    if let Some(arg) = args.first() {
+        return usage_error(&format!("unexpected argument `{}`", arg));
+    }
+
+    let story_data = match load(&file, locale) {
+        Ok(story_data) => story_data,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let graph = StoryGraph::from_story_data(&story_data);
+    let mut game = Game {
+        navigator: PathNavigator::new(&graph),
+        locale,
+        streamer: TextStreamer::from_speed(speed),
+        out: io::stdout(),
+        pending_key: None,
+    };
+
+    let result = game.run();
+    // Always hand the terminal back, even when the game failed
+    let restored = game.leave();
+    match result.and(restored) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+fn load(file: &str, locale: Locale) -> Result<StoryData, String> {
+    let (story_data, _) = StoryLoader::load_validated(file).map_err(|e| format!("{}: {}", file, e))?;
+    if locale == Locale::default() {
+        return Ok(story_data);
+    }
+    let translations = StoryLoader::load_translations(file).map_err(|e| format!("{}: {}", file, e))?;
+    Ok(match translations.get(&locale) {
+        Some(translation) => story_data.localized(translation),
+        None => story_data,
+    })
+}
+
+enum Step {
+    Choose(char),
+    Restart,
+    Quit,
+}
+
+struct Game<'a> {
+    navigator: PathNavigator<'a>,
+    locale: Locale,
+    streamer: TextStreamer,
+    out: io::Stdout,
+    // A key pressed while text streamed that the room should still act on
+    pending_key: Option<KeyEvent>,
+}
+
+impl Game<'_> {
+    fn run(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(self.out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        let mut game_state = self.start();
+        loop {
+            let step = self.play_room(&mut game_state)?;
+            game_state = match step {
+                Step::Choose(key) => {
+                    // Rebuilding from the path also finishes the run in an ending room
+                    let path = format!("{}{}", game_state.get_path(), key);
+                    let Some(mut next) = self.navigator.state_for_path(&path) else {
+                        continue;
+                    };
+                    next.restore_gems(&game_state.gems);
+                    next
+                }
+                Step::Restart => self.start(),
+                Step::Quit => return Ok(()),
+            };
+        }
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        execute!(self.out, cursor::Show, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()
+    }
+
+    fn start(&self) -> GameState {
+        self.navigator
+            .state_for_path("")
+            .unwrap_or_else(|| GameState::with_max_depth(self.navigator.graph().max_depth))
+    }
+
+    fn play_room(&mut self, game_state: &mut GameState) -> io::Result<Step> {
+        let locale = self.locale;
+        let node = self.navigator.get_node(game_state);
+
+        execute!(self.out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        self.print(&format!("{}\n\n", I18n::t(locale, "app.title")))?;
+        self.print(&AsciiTreeGenerator::side_by_side(
+            &AsciiTreeGenerator::generate_tree(game_state, locale),
+            &AsciiTreeGenerator::generate_statistics(game_state, locale),
+            PANEL_GAP,
+        ))?;
+        self.print("\n")?;
+
+        if let Some(story) = &node.story {
+            self.print(&format!("## {}\n\n", story.title))?;
+            if !self.stream(&story.story)? {
+                return Ok(Step::Quit);
+            }
+            self.print("\n\n")?;
+        }
+        if let Some(intro) = &node.intro {
+            self.print(&format!("{}\n{}\n\n", intro.title, intro.story))?;
+        }
+
+        // Ending rooms can hold a gem too
+        let gem = node.story.as_ref().and_then(|story| story.gem.clone());
+        if let Some(gem) = &gem {
+            let key = if game_state.has_gem(game_state.get_path()) { "tui.gem_collected" } else { "tui.gem" };
+            self.print(&format!("{}\n\n", I18n::format(locale, key, &[("name", &gem.name), ("year", &gem.year)])))?;
+        }
+
+        match &node.choice {
+            Some(choice) => {
+                self.print_choice(choice)?;
+                loop {
+                    let key = self.next_key()?;
+                    if let Some(step) = self.choice_step(choice, &key) {
+                        return Ok(step);
+                    }
+                    self.collect_gem(game_state, gem.as_ref(), &key)?;
+                }
+            }
+            None => {
+                if let Some(final_story) = &node.final_story {
+                    self.print(&format!("## {}\n\n", final_story.title))?;
+                    if !self.stream(&final_story.story)? {
+                        return Ok(Step::Quit);
+                    }
+                    self.print("\n\n")?;
+                }
+                if let Some(scenario) = node.story.as_ref().and_then(|story| story.scenario) {
+                    self.print(&format!(
+                        "🔭 {}\n{}\n\n",
+                        I18n::format(locale, "scenario.led_to", &[("name", &scenario.name(locale))]),
+                        scenario.description(locale)
+                    ))?;
+                }
+                self.print(&format!("{}\n", I18n::t(locale, "tui.again")))?;
+                loop {
+                    let key = self.next_key()?;
+                    if is_quit(&key) {
+                        return Ok(Step::Quit);
+                    }
+                    if key.code == KeyCode::Enter {
+                        return Ok(Step::Restart);
+                    }
+                    self.collect_gem(game_state, gem.as_ref(), &key)?;
+                }
+            }
+        }
+    }
+
+    fn print_choice(&mut self, choice: &ChoiceData) -> io::Result<()> {
+        self.print(&format!("{}\n", choice.title))?;
+        if !choice.story.is_empty() {
+            self.print(&format!("{}\n", choice.story))?;
+        }
+        self.print("\n")?;
+        for option in &choice.options {
+            let icon = option.choice_type().map_or("❓", |choice_type| choice_type.icon());
+            self.print(&format!("  [{}] {} {}\n", option.key, icon, option.label))?;
+        }
+        self.print(&format!("\n{}\n", I18n::t(self.locale, "tui.choose")))
+    }
+
+    // Option keys win over Q, so a story may still offer a Q option
+    fn choice_step(&self, choice: &ChoiceData, key: &KeyEvent) -> Option<Step> {
+        if let KeyCode::Char(c) = key.code {
+            if let Some(Shortcut::Choose(choice_type)) = Shortcut::from_key(&c.to_string()) {
+                if let Some(option) = choice.option(choice_type) {
+                    return Some(Step::Choose(option.key));
+                }
+            }
+        }
+        is_quit(key).then_some(Step::Quit)
+    }
+
+    fn collect_gem(&mut self, game_state: &mut GameState, gem: Option<&TimeGem>, key: &KeyEvent) -> io::Result<()> {
+        let Some(gem) = gem.filter(|_| key.code == KeyCode::Char(GEM_KEY)) else {
+            return Ok(());
+        };
+        if game_state.collect_gem(gem.clone()) {
+            self.print(&format!(
+                "{}\n",
+                I18n::format(self.locale, "tui.gem_collected", &[("name", &gem.name), ("year", &gem.year)])
+            ))?;
+        }
+        Ok(())
+    }
+
+    // Keys pressed during the text come first, so a choice made early isn't lost
+    fn next_key(&mut self) -> io::Result<KeyEvent> {
+        match self.pending_key.take() {
+            Some(key) => Ok(key),
+            None => read_key(),
+        }
+    }
+
+    // Prints text chunk by chunk; Space shows the rest at once. `false` means quit
+    fn stream(&mut self, text: &str) -> io::Result<bool> {
+        let chunks = self.streamer.smart_chunks(text);
+        let hint_shown = self.streamer.delay_ms > 0;
+        if hint_shown {
+            self.print(&format!("({})\n\n", I18n::t(self.locale, "tui.skip_hint")))?;
+        }
+
+        let mut skipped = !hint_shown;
+        for chunk in &chunks {
+            self.print(chunk)?;
+            if skipped || !event::poll(self.streamer.chunk_delay(chunk))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if is_quit(&key) {
+                    return Ok(false);
+                }
+                skipped = matches!(key.code, KeyCode::Char(' ') | KeyCode::Enter);
+                if !skipped {
+                    self.pending_key = Some(key);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    // Raw mode doesn't return the carriage on a newline
+    fn print(&mut self, text: &str) -> io::Result<()> {
+        self.out.write_all(text.replace('\n', "\r\n").as_bytes())?;
+        self.out.flush()
+    }
+}
+
+fn read_key() -> io::Result<KeyEvent> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+fn is_quit(key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q' | 'Q') => true,
+        KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
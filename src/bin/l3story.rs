@@ -0,0 +1,268 @@
+// Story authoring tool; shares the loader, validator and models with the server
+use l3_story_game::models::{Locale, Scenario, StoryData, StoryGraph, StoryTranslation};
+use l3_story_game::services::{NodeColoring, StoryExporter, StoryLoader, StoryScaffold, StoryStats, StoryValidator, MIN_STORY_CHARS};
+use l3_story_game::utils::{take_flag, take_option, AsciiTreeGenerator, TreeLayout, TreeOptions};
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::process::ExitCode;
+
+const DEFAULT_STORY_FILE: &str = "../docs/FM_STORY.toml";
+
+const USAGE: &str = "Usage: l3story [--file FM_STORY.toml] [--color plain|length|validation]
+               [--depth N] [--titles] [--all] <command>
+
+Commands:
+  validate          Check the story file and list every problem
+  stats             Character counts per level and per path, endings per scenario
+  scenarios         Which Life 3.0 scenario every ending path stands for
+  tree              Every run from the start with room titles
+  paths [path]      Every run as a tree of choices, folded around a path
+  show [path]       The room a choice path leads to, e.g. `show RBR`
+  scaffold [path]   Append empty sections for missing branches below a path
+  export dot        The whole story as a Graphviz digraph
+  export mermaid    The whole story as a Mermaid flowchart
+  export svg [path] The sidebar's story tree as an SVG image
+
+The story file defaults to $L3_STORY_FILE, then ../docs/FM_STORY.toml.
+--color fills exported nodes by story length or by validation result.
+--depth limits how many levels `tree`, `paths` and `export svg` draw, --titles
+adds room titles and --all opens every branch instead of only those
+along the path.";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let file = match take_option(&mut args, "--file") {
+        Ok(Some(file)) => file,
+        Ok(None) => env::var("L3_STORY_FILE").unwrap_or_else(|_| DEFAULT_STORY_FILE.to_string()),
+        Err(message) => return usage_error(&message),
+    };
+    let coloring = match take_option(&mut args, "--color") {
+        Ok(Some(name)) => match NodeColoring::from_name(&name) {
+            Some(coloring) => coloring,
+            None => return usage_error(&format!("unknown coloring `{}`", name)),
+        },
+        Ok(None) => NodeColoring::default(),
+        Err(message) => return usage_error(&message),
+    };
+
+    let depth = match take_option(&mut args, "--depth") {
+        Ok(Some(depth)) => match depth.parse::<usize>() {
+            Ok(depth) => Some(depth),
+            Err(_) => return usage_error(&format!("--depth needs a number, not `{}`", depth)),
+        },
+        Ok(None) => None,
+        Err(message) => return usage_error(&message),
+    };
+    let titles = take_flag(&mut args, "--titles");
+    let expand_all = take_flag(&mut args, "--all");
+
+    let tree_options = |graph: &StoryGraph| TreeOptions {
+        depth: depth.unwrap_or(graph.max_depth),
+        collapse: !expand_all,
+        titles,
+    };
+
+    let path = args.get(1).map(String::as_str).unwrap_or_default();
+    let result = match args.first().map(String::as_str) {
+        Some("validate") => validate(&file),
+        Some("stats") => load(&file).map(|story_data| stats(&story_data)),
+        Some("scenarios") => load(&file).map(|story_data| scenarios(&story_data)),
+        Some("tree") => load(&file).map(|story_data| {
+            let graph = StoryGraph::from_story_data(&story_data);
+            tree(&graph, TreeOptions { collapse: false, titles: true, ..tree_options(&graph) });
+        }),
+        Some("paths") => load(&file).map(|story_data| {
+            let graph = StoryGraph::from_story_data(&story_data);
+            print!("{}", AsciiTreeGenerator::generate_full_tree(&graph, path, tree_options(&graph)));
+        }),
+        Some("show") => load(&file).and_then(|story_data| show(&story_data, path)),
+        Some("scaffold") => scaffold(&file, path),
+        Some("export") => match path {
+            "dot" => load(&file).map(|story_data| print!("{}", StoryExporter::to_dot(&story_data, coloring))),
+            "mermaid" => load(&file).map(|story_data| print!("{}", StoryExporter::to_mermaid(&story_data, coloring))),
+            "svg" => load(&file).map(|story_data| {
+                let graph = StoryGraph::from_story_data(&story_data);
+                let svg_path = args.get(2).map(String::as_str).unwrap_or_default();
+                print!("{}", TreeLayout::build(&graph, svg_path, tree_options(&graph)).to_svg());
+            }),
+            _ => return usage_error("export needs a format: dot, mermaid or svg"),
+        },
+        Some("help" | "--help" | "-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => return usage_error(&format!("unknown command `{}`", command)),
+        None => return usage_error("missing command"),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+fn read(file: &str) -> Result<String, String> {
+    std::fs::read_to_string(file).map_err(|e| format!("can't read {}: {}", file, e))
+}
+
+// Stats, tree and show still work on a half-written story, as long as it parses
+fn load(file: &str) -> Result<StoryData, String> {
+    let (story_data, report) = StoryValidator::validate_str(&read(file)?);
+    story_data.ok_or_else(|| {
+        report
+            .errors()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+fn validate(file: &str) -> Result<(), String> {
+    let (story_data, report) = StoryValidator::validate_str(&read(file)?);
+    for diagnostic in &report.diagnostics {
+        println!("{}", diagnostic);
+    }
+    let errors = report.errors().count();
+    let warnings = report.warnings().count();
+    if errors > 0 {
+        return Err(format!("{}: {} errors, {} warnings", file, errors, warnings));
+    }
+    println!("{}: ok, {} warnings", file, warnings);
+    if let Some(story_data) = story_data {
+        translations(file, &story_data)?;
+    }
+    Ok(())
+}
+
+// Untranslated sections fall back to the default locale, so list them rather than let them pass quietly
+fn translations(file: &str, story_data: &StoryData) -> Result<(), String> {
+    let translations = StoryLoader::load_translations(file).map_err(|e| format!("{}: {}", file, e))?;
+    let sections = StoryTranslation::default().untranslated(story_data).len();
+    for locale in Locale::ALL.into_iter().filter(|locale| translations.contains_key(locale)) {
+        let missing = translations[&locale].untranslated(story_data);
+        for key in &missing {
+            println!("untranslated ({}): {}", locale.code(), key);
+        }
+        println!("{} pack: {} of {} sections translated", locale.code(), sections - missing.len(), sections);
+    }
+    Ok(())
+}
+
+fn stats(story_data: &StoryData) {
+    let stats = StoryStats::collect(story_data);
+
+    println!("{:>5} {:>6} {:>9} {:>7} {:>7} {:>7} {:>6}", "level", "rooms", "chars", "avg", "min", "max", "short");
+    for level in &stats.levels {
+        println!(
+            "{:>5} {:>6} {:>9} {:>7} {:>7} {:>7} {:>6}",
+            level.level,
+            level.rooms,
+            level.total_chars,
+            level.total_chars / level.rooms,
+            level.min_chars,
+            level.max_chars,
+            level.short_rooms
+        );
+    }
+    println!("{:>5} {:>6} {:>9}", "all", stats.rooms.len(), stats.total_chars());
+    println!();
+
+    let width = stats.rooms.iter().map(|room| room.key.len()).max().unwrap_or(0);
+    for room in &stats.rooms {
+        let flag = if room.chars < MIN_STORY_CHARS { " short" } else { "" };
+        println!("{:<width$} {:>6}{:<6}  {}", room.key, room.chars, flag, room.title, width = width);
+    }
+    println!();
+    print_scenario_counts(&stats);
+}
+
+fn print_scenario_counts(stats: &StoryStats) {
+    println!("{:<20} {:>7}", "scenario", "endings");
+    for (scenario, count) in stats.scenario_counts() {
+        println!("{:<20} {:>7}", scenario.map_or("(untagged)", Scenario::key), count);
+    }
+    println!("{:<20} {:>7}", "all", stats.endings.len());
+}
+
+fn scenarios(story_data: &StoryData) {
+    let stats = StoryStats::collect(story_data);
+    print_scenario_counts(&stats);
+    for (scenario, count) in stats.scenario_counts() {
+        if count == 0 {
+            continue;
+        }
+        println!("\n{}", scenario.map_or("(untagged)", Scenario::key));
+        for ending in stats.endings.iter().filter(|ending| ending.scenario == scenario) {
+            println!("  {:<8} {}", ending.path, ending.title);
+        }
+    }
+}
+
+fn tree(graph: &StoryGraph, options: TreeOptions) {
+    print!("{}", AsciiTreeGenerator::generate_full_tree(graph, "", options));
+    println!("{} rooms, {} endings", graph.depths().len(), graph.ending_count());
+}
+
+fn show(story_data: &StoryData, path: &str) -> Result<(), String> {
+    let graph = StoryGraph::from_story_data(story_data);
+    let id = graph
+        .walk(path)
+        .and_then(|ids| ids.last().copied())
+        .ok_or_else(|| format!("path `{}` doesn't lead to a room", path))?;
+    let node = graph.node(id).ok_or_else(|| format!("no room `{}`", id))?;
+
+    println!("[{}] level {}", story_data.section_key(id), path.chars().count());
+    println!("{}", node.content.title);
+    println!();
+    println!("{}", node.content.story);
+    println!();
+    println!("{} characters", StoryValidator::story_chars(&node.content.story));
+    if let Some(gem) = &node.content.gem {
+        println!("gem: {} ({}) {}", gem.name, gem.year, gem.description);
+    }
+    match node.choice.as_ref().filter(|_| !node.is_ending()) {
+        Some(choice) => {
+            println!("choice: {}", choice.title);
+            for option in &choice.options {
+                let effects: Vec<String> = option.effects.iter().map(|(axis, delta)| format!("{} {:+}", axis, delta)).collect();
+                let effects = if effects.is_empty() { String::new() } else { format!("  [{}]", effects.join(", ")) };
+                println!("  {} {} -> {}{}", option.key, option.label, option.target.as_deref().unwrap_or("?"), effects);
+            }
+        }
+        None => {
+            let key = graph.ending_key(path).map_or_else(|| "FM_NOEND".to_string(), |key| format!("FM_ENDING.{}", key));
+            println!("ending: [{}] {}", key, graph.ending_at(path).title);
+        }
+    }
+    Ok(())
+}
+
+fn scaffold(file: &str, path: &str) -> Result<(), String> {
+    let story_data = load(file)?;
+    let sections = StoryScaffold::missing_sections(&story_data, path)
+        .ok_or_else(|| format!("path `{}` doesn't lead to a room", path))?;
+    if sections.is_empty() {
+        println!("nothing missing below `{}`", path);
+        return Ok(());
+    }
+
+    let mut story_file = OpenOptions::new()
+        .append(true)
+        .open(file)
+        .map_err(|e| format!("can't open {}: {}", file, e))?;
+    story_file
+        .write_all(sections.as_bytes())
+        .map_err(|e| format!("can't write {}: {}", file, e))?;
+    print!("{}", sections);
+    println!("\nappended {} sections to {}", sections.matches("\n[").count(), file);
+    Ok(())
+}
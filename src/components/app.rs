@@ -1,106 +1,90 @@
 use leptos::*;
+use leptos_meta::*;
 use leptos_router::*;
-use crate::models::{GameState, StoryData, ChoiceType};
-use crate::services::{StoryLoader, PathNavigator};
-use crate::components::{StoryDisplay, ChoiceButtons, StoryTree, ControlPanel};
+use crate::models::{ChoiceStats, CollectedGem, Locale, LoopMemory, TextSpeed};
+use crate::services::SaveManager;
+use crate::utils::I18n;
+use crate::components::{GamePage, NotFound, PendingChoice, VoteJoinPage, VotePage, VotingHost};
 
 #[component]
 pub fn App() -> impl IntoView {
-    let (story_data, set_story_data) = create_signal(None::<StoryData>);
-    let (game_state, set_game_state) = create_signal(GameState::new());
-    let (loading, set_loading) = create_signal(true);
-    let (error, set_error) = create_signal(None::<String>);
+    provide_meta_context();
 
-    // Load story data on component mount
+    // The gem inventory outlives page navigation, so it lives above the router
+    provide_context(create_rw_signal(Vec::<CollectedGem>::new()));
+    provide_context(VotingHost::default());
+    // How others chose at the player's last decision, shown once the next node is up
+    provide_context(create_rw_signal(None::<ChoiceStats>));
+    provide_context(PendingChoice(create_rw_signal(None)));
+
+    // Completed runs and the text speed survive loops and reloads; localStorage is read after mount
+    let memory = create_rw_signal(LoopMemory::default());
+    provide_context(memory);
+    let text_speed = create_rw_signal(TextSpeed::default());
+    provide_context(text_speed);
     create_effect(move |_| {
-        spawn_local(async move {
-            match StoryLoader::load_default() {
-                Ok(data) => {
-                    set_story_data.set(Some(data));
-                    set_loading.set(false);
-                }
-                Err(e) => {
-                    set_error.set(Some(format!("Failed to load story data: {}", e)));
-                    set_loading.set(false);
-                }
-            }
-        });
+        memory.set(SaveManager::load_memory());
+        text_speed.set(SaveManager::load_text_speed());
     });
 
-    let make_choice = move |choice_type: ChoiceType| {
-        set_game_state.update(|state| {
-            state.add_choice(choice_type);
-        });
-    };
-
-    let reset_game = move || {
-        set_game_state.update(|state| {
-            state.reset();
-        });
-    };
-
     view! {
         <Router>
-            <div class="app-container">
-                <header class="app-header">
-                    <h1>"[L3]未来之门"</h1>
-                    <p>"基于 Life 3.0 的交互式故事游戏"</p>
-                </header>
-                
-                <main class="app-main">
-                    {move || {
-                        if loading.get() {
-                            view! {
-                                <div class="loading">
-                                    <p>"加载故事数据中..."</p>
-                                </div>
-                            }.into_view()
-                        } else if let Some(error_msg) = error.get() {
-                            view! {
-                                <div class="error">
-                                    <p>"错误: " {error_msg}</p>
-                                </div>
-                            }.into_view()
-                        } else if let Some(data) = story_data.get() {
-                            let navigator = PathNavigator::new(&data);
-                            view! {
-                                <div class="game-container">
-                                    <div class="game-content">
-                                        <StoryDisplay 
-                                            story_data=data.clone()
-                                            game_state=game_state.get()
-                                        />
-                                        
-                                        <ChoiceButtons 
-                                            story_data=data.clone()
-                                            game_state=game_state.get()
-                                            on_choice=make_choice
-                                        />
-                                    </div>
-                                    
-                                    <aside class="game-sidebar">
-                                        <StoryTree 
-                                            story_data=data.clone()
-                                            game_state=game_state.get()
-                                        />
-                                        
-                                        <ControlPanel 
-                                            game_state=game_state.get()
-                                            on_reset=reset_game
-                                        />
-                                    </aside>
-                                </div>
-                            }.into_view()
-                        } else {
-                            view! {
-                                <div class="empty">
-                                    <p>"无法加载故事数据"</p>
-                                </div>
-                            }.into_view()
-                        }
-                    }}
-                </main>
-            </div>
+            <AppShell/>
         </Router>
     }
-}
\ No newline at end of file
+}
+
+// `?lang=` wins over the browser's preference; links carry it forward
+pub fn use_url_locale() -> Memo<Option<Locale>> {
+    let query = use_query_map();
+    create_memo(move |_| query.with(|q| q.get("lang").and_then(|code| Locale::from_code(code))))
+}
+
+#[cfg(feature = "ssr")]
+fn preferred_locale() -> Locale {
+    use_context::<axum::http::request::Parts>()
+        .and_then(|parts| {
+            let header = parts.headers.get(axum::http::header::ACCEPT_LANGUAGE)?;
+            Locale::negotiate(header.to_str().ok()?)
+        })
+        .unwrap_or_default()
+}
+
+// The server already negotiated Accept-Language into <html lang>, so hydrate from that
+#[cfg(not(feature = "ssr"))]
+fn preferred_locale() -> Locale {
+    document()
+        .document_element()
+        .and_then(|root| root.get_attribute("lang"))
+        .and_then(|lang| Locale::from_code(&lang))
+        .unwrap_or_default()
+}
+
+#[component]
+fn AppShell() -> impl IntoView {
+    let url_locale = use_url_locale();
+    let preferred = preferred_locale();
+    let locale = Signal::derive(move || url_locale.get().unwrap_or(preferred));
+    provide_context(locale);
+
+    view! {
+        <Html lang=move || locale.get().code()/>
+        <Title text=move || I18n::t(locale.get(), "app.title")/>
+        <div class="app-container">
+            <header class="app-header">
+                <h1>{move || I18n::t(locale.get(), "app.title")}</h1>
+                <p>{move || I18n::t(locale.get(), "app.tagline")}</p>
+            </header>
+
+            <main class="app-main">
+                <Routes>
+                    <Route path="/" view=GamePage ssr=SsrMode::Async/>
+                    <Route path="/play/:path" view=GamePage ssr=SsrMode::Async/>
+                    <Route path="/vote" view=VoteJoinPage/>
+                    <Route path="/vote/:code" view=VotePage/>
+                    <Route path="/*any" view=NotFound/>
+                </Routes>
+            </main>
+        </div>
+    }
+}
@@ -1,47 +1,66 @@
 use leptos::*;
-use crate::models::{StoryData, GameState, ChoiceType};
-use crate::services::PathNavigator;
+use std::rc::Rc;
+use crate::models::{StoryNode, GameState, ChoiceType, Locale};
+use crate::utils::{I18n, Shortcut};
+use crate::components::{is_static_render, static_href};
 
 #[component]
 pub fn ChoiceButtons(
-    story_data: StoryData,
+    node: StoryNode,
     game_state: GameState,
+    explored: Vec<ChoiceType>,
     on_choice: impl Fn(ChoiceType) + 'static,
 ) -> impl IntoView {
-    let navigator = PathNavigator::new(&story_data);
-    let current_choice = navigator.get_current_choice(&game_state);
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let current_choice = node.choice;
+    let on_choice = Rc::new(on_choice);
+    let static_render = is_static_render();
     
     view! {
         <div class="choice-buttons">
             {if let Some(choice) = current_choice {
                 view! {
-                    <div class="button-container">
-                        <button 
-                            class="choice-button red-button"
-                            on:click=move |_| on_choice(ChoiceType::Red)
-                        >
-                            <span class="button-text">{choice.red.clone()}</span>
-                        </button>
-                        
-                        <button 
-                            class="choice-button blue-button"
-                            on:click=move |_| on_choice(ChoiceType::Blue)
-                        >
-                            <span class="button-text">{choice.blue.clone()}</span>
-                        </button>
+                    <div class="button-container" data-options=choice.options.len()>
+                        {choice.options.into_iter().filter_map(|option| {
+                            let choice_type = option.choice_type()?;
+                            let is_explored = explored.contains(&choice_type);
+                            let class = format!("choice-button {}-button", option.color);
+                            if static_render {
+                                let href = static_href(&format!("{}{}", node.path, option.key));
+                                return Some(view! {
+                                    <a class=class href=href>
+                                        <span class="button-text">{option.label}</span>
+                                    </a>
+                                }.into_view());
+                            }
+                            let on_choice = on_choice.clone();
+                            let keys = Shortcut::Choose(choice_type).aria_keys();
+                            Some(view! {
+                                <button 
+                                    class=class
+                                    class:explored=is_explored
+                                    aria-keyshortcuts=keys.clone()
+                                    on:click=move |_| on_choice(choice_type)
+                                >
+                                    <kbd class="key-hint" aria-hidden="true">{keys}</kbd>
+                                    <span class="button-text">{option.label}</span>
+                                    {is_explored.then(|| view! { <span class="explored-hint">{I18n::t(locale, "choice.explored")}</span> })}
+                                </button>
+                            }.into_view())
+                        }).collect_view()}
                     </div>
                 }.into_view()
             } else if game_state.is_complete() {
                 view! {
                     <div class="completion-message">
-                        <p>"故事已完成！"</p>
-                        <p>"你的选择路径: " {game_state.get_path()}</p>
+                        <p>{I18n::t(locale, "choice.complete")}</p>
+                        <p>{I18n::t(locale, "choice.your_path")} {game_state.get_path().to_string()}</p>
                     </div>
                 }.into_view()
             } else {
                 view! {
                     <div class="no-choices">
-                        <p>"加载选择中..."</p>
+                        <p>{I18n::t(locale, "choice.loading")}</p>
                     </div>
                 }.into_view()
             }}
@@ -0,0 +1,43 @@
+use leptos::*;
+use crate::models::{ChoiceStats, ChoiceType, Locale};
+use crate::utils::I18n;
+
+// The path a decision leads to, until that room has loaded and the decision is counted
+#[derive(Debug, Clone, Copy)]
+pub struct PendingChoice(pub RwSignal<Option<String>>);
+
+// How other players chose at the decision the player just made
+#[component]
+pub fn ChoiceShareNote(
+    stats: ChoiceStats,
+    key: char,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let Some(percent) = stats.percent(key) else {
+        return ().into_view();
+    };
+    let headline = if stats.total() == 1 {
+        I18n::t(locale, "choice.share_first").to_string()
+    } else {
+        let choice = ChoiceType::from_char(key).map_or_else(|| key.to_string(), |choice_type| choice_type.name(locale));
+        I18n::format(locale, "choice.share", &[("percent", &percent), ("choice", &choice)])
+    };
+
+    view! {
+        <section class="choice-share" aria-label=I18n::t(locale, "choice.share_title")>
+            <p class="choice-share-headline">{headline}</p>
+            <ul class="choice-share-bars">
+                {stats.shares.iter().map(|share| {
+                    let percent = stats.percent(share.key).unwrap_or(0);
+                    view! {
+                        <li class=format!("choice-share-row {}-share", share.color) class:mine={share.key == key}>
+                            <span class="choice-share-label">{share.label.clone()}</span>
+                            <span class="choice-share-bar" style=format!("width: {}%", percent)></span>
+                            <span class="choice-share-percent">{format!("{}%", percent)}</span>
+                        </li>
+                    }
+                }).collect_view()}
+            </ul>
+        </section>
+    }.into_view()
+}
@@ -1,61 +1,231 @@
 use leptos::*;
-use crate::models::GameState;
+use std::rc::Rc;
+use crate::models::{GameState, Locale, SaveSlot, TextSpeed};
+use crate::services::SaveManager;
+use crate::utils::{I18n, Shortcut};
+use crate::components::MenuFocus;
 
 #[component]
 pub fn ControlPanel(
-    game_state: GameState,
+    // Live state, so picking up a gem doesn't remount the panel and drop its slot list
+    #[prop(into)] game_state: Signal<GameState>,
+    ending_count: usize,
+    // Title of the ending the run finished in
+    ending: Option<String>,
     on_reset: impl Fn() + 'static,
+    on_load: impl Fn(GameState) + 'static,
 ) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let (slots, set_slots) = create_signal(Vec::<SaveSlot>::new());
+    let (show_slots, set_show_slots) = create_signal(false);
+    let (slot_name, set_slot_name) = create_signal(String::new());
+    let (message, set_message) = create_signal(None::<String>);
+    let on_load = Rc::new(on_load);
+    let text_speed = expect_context::<RwSignal<TextSpeed>>();
+    let MenuFocus(menu_focus) = expect_context::<MenuFocus>();
+    let panel_title = create_node_ref::<html::H3>();
+
+    // Esc lands on the panel heading, so a stray Enter can't reset the run;
+    // Tab goes on from there. Nothing is focused on mount
+    create_effect(move |ran: Option<()>| {
+        menu_focus.track();
+        if ran.is_some() {
+            if let Some(title) = panel_title.get_untracked() {
+                let _ = title.focus();
+            }
+        }
+    });
+
+    // An unreadable slot list is reported; saving over it is refused too
+    let refresh_slots = move || match SaveManager::list_slots() {
+        Ok(list) => set_slots.set(list),
+        Err(e) => {
+            set_slots.set(Vec::new());
+            set_message.set(Some(I18n::format(locale, "control.slots_unreadable", &[("error", &e)])));
+        }
+    };
+
+    // localStorage only exists in the browser, so read it after mount
+    create_effect(move |_| refresh_slots());
+
+    let save_game = move |_| {
+        let state = game_state.get_untracked();
+        let name = slot_name.get();
+        let name = if name.trim().is_empty() {
+            I18n::format(locale, "control.default_slot", &[("path", &state.get_path())])
+        } else {
+            name
+        };
+        match SaveManager::save(&name, &state) {
+            Ok(slot) => {
+                set_message.set(Some(I18n::format(locale, "control.saved", &[("name", &slot.name)])));
+                refresh_slots();
+                set_slot_name.set(String::new());
+            }
+            Err(e) => set_message.set(Some(I18n::format(locale, "control.save_failed", &[("error", &e)]))),
+        }
+    };
+
+    let change_speed = move |ev| {
+        let Some(speed) = TextSpeed::from_key(&event_target_value(&ev)) else {
+            return;
+        };
+        text_speed.set(speed);
+        if let Err(e) = SaveManager::store_text_speed(speed) {
+            logging::error!("Failed to store text speed: {}", e);
+        }
+    };
+
+    let path_is_empty = move || game_state.with(|state| state.get_path().is_empty());
+
     view! {
         <div class="control-panel">
-            <h3 class="panel-title">"游戏控制"</h3>
-            
+            <h3
+                class="panel-title"
+                tabindex="-1"
+                node_ref=panel_title
+                aria-keyshortcuts=Shortcut::FocusMenu.aria_keys()
+            >
+                {I18n::t(locale, "control.title")}
+            </h3>
+
             <div class="control-buttons">
-                <button 
+                <button
                     class="control-button reset-button"
                     on:click=move |_| on_reset()
                 >
-                    "🔄 重新开始"
+                    {I18n::t(locale, "control.reset")}
                 </button>
-                
-                <button 
+
+                <input
+                    class="slot-name-input"
+                    type="text"
+                    placeholder=I18n::t(locale, "control.slot_placeholder")
+                    prop:value=move || slot_name.get()
+                    on:input=move |ev| set_slot_name.set(event_target_value(&ev))
+                />
+
+                <button
                     class="control-button save-button"
-                    disabled=move || game_state.get_path().is_empty()
+                    disabled=path_is_empty
+                    on:click=save_game
                 >
-                    "💾 保存进度"
+                    {I18n::t(locale, "control.save")}
                 </button>
-                
-                <button 
+
+                <button
                     class="control-button load-button"
+                    on:click=move |_| {
+                        refresh_slots();
+                        set_show_slots.update(|show| *show = !*show);
+                    }
                 >
-                    "📁 加载进度"
+                    {I18n::t(locale, "control.load")}
                 </button>
             </div>
-            
+
+            <label class="text-speed">
+                <span class="stat-label">{I18n::t(locale, "control.text_speed")}</span>
+                <select class="text-speed-select" on:change=change_speed>
+                    {TextSpeed::ALL.into_iter().map(|speed| view! {
+                        <option value=speed.key() selected=move || text_speed.get() == speed>
+                            {speed.name(locale)}
+                        </option>
+                    }).collect_view()}
+                </select>
+            </label>
+
+            {move || message.get().map(|msg| view! { <p class="save-message">{msg}</p> })}
+
+            {move || {
+                if !show_slots.get() {
+                    return view! {}.into_view();
+                }
+                let slots = slots.get();
+                if slots.is_empty() {
+                    return view! {
+                        <div class="save-slots">
+                            <p class="no-slots">{I18n::t(locale, "control.no_slots")}</p>
+                        </div>
+                    }.into_view();
+                }
+                let on_load = on_load.clone();
+                view! {
+                    <ul class="save-slots">
+                        {slots.into_iter().map(|slot| {
+                            let on_load = on_load.clone();
+                            let name = slot.name.clone();
+                            let delete_name = slot.name.clone();
+                            let compatible = slot.is_compatible();
+                            view! {
+                                <li class="save-slot">
+                                    <div class="slot-info">
+                                        <span class="slot-name">{slot.name.clone()}</span>
+                                        <span class="slot-time">{SaveManager::format_timestamp(slot.saved_at, locale)}</span>
+                                        <span class="slot-path">{slot.path_preview(locale)}</span>
+                                    </div>
+                                    <button
+                                        class="control-button slot-load-button"
+                                        disabled=!compatible
+                                        on:click=move |_| {
+                                            match SaveManager::load(&name) {
+                                                Ok(state) => {
+                                                    set_message.set(Some(I18n::format(locale, "control.loaded", &[("name", &name)])));
+                                                    set_show_slots.set(false);
+                                                    on_load(state);
+                                                }
+                                                Err(e) => set_message.set(Some(I18n::format(locale, "control.load_failed", &[("error", &e)]))),
+                                            }
+                                        }
+                                    >
+                                        {I18n::t(locale, "control.load_slot")}
+                                    </button>
+                                    <button
+                                        class="control-button slot-delete-button"
+                                        on:click=move |_| {
+                                            if let Err(e) = SaveManager::delete(&delete_name) {
+                                                set_message.set(Some(I18n::format(locale, "control.delete_failed", &[("error", &e)])));
+                                            }
+                                            refresh_slots();
+                                        }
+                                    >
+                                        {I18n::t(locale, "control.delete_slot")}
+                                    </button>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                }.into_view()
+            }}
+
             <div class="game-stats">
-                <h4>"游戏统计"</h4>
+                <h4>{I18n::t(locale, "control.stats")}</h4>
                 <div class="stats-grid">
                     <div class="stat-item">
-                        <span class="stat-label">"已做选择:"</span>
-                        <span class="stat-value">{game_state.get_level()}</span>
+                        <span class="stat-label">{I18n::t(locale, "control.choices_made")}</span>
+                        <span class="stat-value">{move || game_state.with(GameState::get_level)}</span>
                     </div>
                     <div class="stat-item">
-                        <span class="stat-label">"剩余选择:"</span>
-                        <span class="stat-value">{6 - game_state.get_level()}</span>
+                        <span class="stat-label">{I18n::t(locale, "control.choices_left")}</span>
+                        <span class="stat-value">{move || game_state.with(|state| state.get_max_depth().saturating_sub(state.get_level()))}</span>
                     </div>
                     <div class="stat-item">
-                        <span class="stat-label">"路径长度:"</span>
-                        <span class="stat-value">{game_state.get_path().len()}</span>
+                        <span class="stat-label">{I18n::t(locale, "control.path_length")}</span>
+                        <span class="stat-value">{move || game_state.with(|state| state.get_path().len())}</span>
                     </div>
                 </div>
             </div>
-            
-            {if game_state.is_complete() {
+
+            {move || if game_state.with(GameState::is_complete) {
+                let ending = ending.clone();
                 view! {
                     <div class="completion-info">
-                        <h4>"🎉 故事完成!"</h4>
-                        <p>"你的最终路径: " <code>{game_state.get_path()}</code></p>
-                        <p>"这是 126 种可能结局中的一种"</p>
+                        <h4>{I18n::t(locale, "control.complete")}</h4>
+                        {ending.map(|title| view! {
+                            <p class="ending-name">{I18n::format(locale, "control.ending_named", &[("title", &title)])}</p>
+                        })}
+                        <p>{I18n::t(locale, "control.final_path")} <code>{game_state.with(|state| state.get_path().to_string())}</code></p>
+                        <p>{I18n::format(locale, "control.ending_of", &[("count", &ending_count)])}</p>
                     </div>
                 }.into_view()
             } else {
@@ -63,4 +233,4 @@ pub fn ControlPanel(
             }}
         </div>
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,325 @@
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::*;
+use crate::models::{ChoiceStats, GameState, ChoiceType, CollectedGem, Locale, LoopMemory, TimeGem, VotePhase};
+use crate::services::{get_story_node, record_choice, SaveManager};
+use crate::utils::{I18n, Shortcut};
+use crate::components::{use_url_locale, StoryDisplay, ChoiceButtons, ChoiceShareNote, PendingChoice, StoryTree, ControlPanel, GemInventory, HistoryScreen, LoopBack, NotFound, KeyboardLegend, MenuFocus, ScenarioCard, SkipText, VotingHost, VotingPanel, WorldRadar};
+
+pub fn play_href(path: &str, locale: Option<Locale>) -> String {
+    let href = if path.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/play/{}", path)
+    };
+    match locale {
+        Some(locale) => format!("{}?lang={}", href, locale.code()),
+        None => href,
+    }
+}
+
+#[component]
+pub fn GamePage() -> impl IntoView {
+    let params = use_params_map();
+    let path = move || params.with(|p| p.get("path").cloned().unwrap_or_default());
+
+    let locale = expect_context::<Signal<Locale>>();
+    let url_locale = use_url_locale();
+
+    let story_node = create_resource(
+        move || (path(), locale.get()),
+        |(path, locale)| get_story_node(path, locale),
+    );
+
+    // The server hot-reloads the story file; refetch the node we're on when it does
+    create_effect(move |_| {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let Ok(events) = web_sys::EventSource::new("/events/story") else {
+            return;
+        };
+        let on_update = Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |_| story_node.refetch());
+        let _ = events.add_event_listener_with_callback("story-updated", on_update.as_ref().unchecked_ref());
+        on_cleanup(move || {
+            events.close();
+            drop(on_update);
+        });
+    });
+
+    // The URL is the source of truth, so a shared link restores the same node;
+    // the story's depth arrives with the node from the server
+    let path_state = create_memo(move |_| {
+        match story_node.get() {
+            Some(Ok(Some(node))) => node.game_state(),
+            _ => None,
+        }
+    });
+
+    // Gems are tracked separately so picking one up doesn't rebuild the story view
+    let gems = expect_context::<RwSignal<Vec<CollectedGem>>>();
+    let memory = expect_context::<RwSignal<LoopMemory>>();
+    let game_state = create_memo(move |_| {
+        path_state.get().map(|mut state| {
+            gems.with(|gems| state.restore_gems(gems));
+            state
+        })
+    });
+
+    // Only flips when a run ends or restarts, so picking up a gem on the ending
+    // doesn't remount the loop-back countdown
+    let run_complete = create_memo(move |_| {
+        game_state.with(|state| state.as_ref().is_some_and(GameState::is_complete))
+    });
+
+    let navigate = use_navigate();
+    let go_to = Callback::new(move |path: String| {
+        navigate(&play_href(&path, url_locale.get_untracked()), Default::default());
+    });
+
+    // A room's vote moves the presenter's screen on, once per decision
+    let voting = expect_context::<VotingHost>();
+    create_effect(move |was_decided: Option<bool>| {
+        let decided = voting.room.with(|room| match room {
+            Some(view) if matches!(view.phase, VotePhase::Decided { key: Some(_) }) => Some(view.path.clone()),
+            _ => None,
+        });
+        if let Some(decided_path) = &decided {
+            if was_decided != Some(true) && *decided_path != path() {
+                go_to.call(decided_path.clone());
+            }
+        }
+        decided.is_some()
+    });
+
+    // A decision is counted once the room it leads to has loaded, so a failed
+    // navigation counts nothing; the server answers with how everyone chose there
+    let last_decision = expect_context::<RwSignal<Option<ChoiceStats>>>();
+    let PendingChoice(pending_choice) = expect_context::<PendingChoice>();
+    create_effect(move |_| {
+        let reached = story_node.with(|node| match node {
+            Some(Ok(Some(node))) => Some(node.path.clone()),
+            _ => None,
+        });
+        let Some(mut from) = reached.filter(|path| pending_choice.with_untracked(|pending| pending.as_ref() == Some(path))) else {
+            return;
+        };
+        pending_choice.set(None);
+        let Some(key) = from.pop() else {
+            return;
+        };
+        let locale = locale.get_untracked();
+        spawn_local(async move {
+            match record_choice(from, key, locale).await {
+                Ok(stats) => last_decision.set(stats),
+                Err(e) => logging::error!("Failed to record choice: {}", e),
+            }
+        });
+    });
+
+    let make_choice = move |choice_type: ChoiceType| {
+        if let Some(mut state) = path_state.get_untracked() {
+            state.add_choice(choice_type);
+            pending_choice.set(Some(state.get_path().to_string()));
+            go_to.call(state.get_path().to_string());
+        }
+    };
+
+    let skip_text = create_trigger();
+    provide_context(SkipText(skip_text));
+    let menu_focus = create_trigger();
+    provide_context(MenuFocus(menu_focus));
+
+    create_effect(move |_| {
+        use wasm_bindgen::JsCast;
+
+        let handle = window_event_listener(ev::keydown, move |ev| {
+            if ev.ctrl_key() || ev.meta_key() || ev.alt_key() || ev.repeat() {
+                return;
+            }
+            let shortcut = Shortcut::from_key(&ev.key());
+            // Esc is how keyboard users get out of the slot name field, so it comes first
+            if shortcut == Some(Shortcut::FocusMenu) {
+                ev.prevent_default();
+                menu_focus.notify();
+                return;
+            }
+            let target = ev.target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .map(|element| element.tag_name())
+                .unwrap_or_default();
+            // Typing a slot name or picking a speed shouldn't steer the story
+            if matches!(target.as_str(), "INPUT" | "SELECT" | "TEXTAREA") {
+                return;
+            }
+            match shortcut {
+                Some(Shortcut::Choose(choice_type)) => {
+                    // Resources have no untracked read, so stop the key handler subscribing
+                    let offered = untrack(|| story_node.with(|node| {
+                        matches!(node, Some(Ok(Some(node))) if node.choice.as_ref().is_some_and(|choice| choice.option(choice_type).is_some()))
+                    }));
+                    if offered {
+                        ev.prevent_default();
+                        make_choice(choice_type);
+                    }
+                }
+                // Space on a focused button presses it instead
+                Some(Shortcut::SkipText) if !matches!(target.as_str(), "BUTTON" | "A") => {
+                    ev.prevent_default();
+                    skip_text.notify();
+                }
+                _ => {}
+            }
+        });
+        on_cleanup(move || handle.remove());
+    });
+
+    let collect_gem = move |gem: TimeGem| {
+        if let Some(mut state) = game_state.get_untracked() {
+            if state.collect_gem(gem) {
+                gems.set(state.gems);
+            }
+        }
+    };
+
+    let gem_collected = Signal::derive(move || {
+        game_state.with(|state| {
+            state.as_ref().is_some_and(|state| state.has_gem(state.get_path()))
+        })
+    });
+
+    let reset_game = move || {
+        gems.set(Vec::new());
+        go_to.call(String::new());
+    };
+
+    // Finishing a run stores it in the loop memory and starts the next one at the gate
+    let loop_back = move || {
+        if let Some(state) = game_state.get_untracked() {
+            memory.update(|memory| {
+                memory.record_run(&state, js_sys::Date::now());
+            });
+            if let Err(e) = memory.with_untracked(SaveManager::store_memory) {
+                logging::error!("Failed to store loop memory: {}", e);
+            }
+        }
+        gems.set(Vec::new());
+        go_to.call(String::new());
+    };
+
+    let load_game = move |state: GameState| {
+        gems.set(state.gems.clone());
+        go_to.call(state.get_path().to_string());
+    };
+
+    view! {
+        <Transition fallback=move || view! {
+            <div class="loading">
+                <p>{I18n::t(locale.get_untracked(), "app.loading")}</p>
+            </div>
+        }>
+            {move || story_node.get().map(|result| match result {
+                Ok(Some(node)) => {
+                    let locale = locale.get_untracked();
+                    let state = node.game_state().unwrap_or_else(|| GameState::with_max_depth(node.max_depth));
+                    let ending_count = node.ending_count;
+                    let tree = node.tree.clone();
+                    let layout = node.layout.clone();
+                    let axes = node.axes.clone();
+                    let node_path = node.path.clone();
+                    let has_choice = node.choice.is_some();
+                    let scenario = node.story.as_ref().and_then(|story| story.scenario);
+                    let ending = node.final_story.as_ref().map(|story| story.title.clone());
+                    let options = node.choice.as_ref()
+                        .map(|choice| choice.options.clone())
+                        .unwrap_or_default();
+                    let title = node.story.as_ref()
+                        .map(|story| story.title.clone())
+                        .unwrap_or_else(|| I18n::t(locale, "app.gate").to_string());
+                    // The panels below stay mounted while gems change the run
+                    let live_state = {
+                        let state = state.clone();
+                        Signal::derive(move || game_state.get().unwrap_or_else(|| state.clone()))
+                    };
+                    let description = if node.path.is_empty() {
+                        format!("{} · {}", title, I18n::t(locale, "app.tagline"))
+                    } else {
+                        I18n::format(locale, "app.description_path", &[("title", &title), ("path", &node.path)])
+                    };
+                    view! {
+                        <Title text=format!("{} | {}", title, I18n::t(locale, "app.title"))/>
+                        <Meta name="description" content=description/>
+                        
+                        <div class="game-container">
+                            <div class="game-content">
+                                <StoryDisplay
+                                    node=node.clone()
+                                    gem_collected=gem_collected
+                                    on_collect_gem=collect_gem
+                                />
+                                
+                                {
+                                    let here = node.path.clone();
+                                    move || last_decision.get().and_then(|stats| {
+                                        let key = stats.step_to(&here)?;
+                                        Some(view! { <ChoiceShareNote stats=stats key=key /> })
+                                    })
+                                }
+                                
+                                {
+                                    let state = state.clone();
+                                    move || view! {
+                                        <ChoiceButtons 
+                                            node=node.clone()
+                                            game_state=state.clone()
+                                            explored=memory.with(|memory| memory.explored_choices(state.get_path()))
+                                            on_choice=make_choice
+                                        />
+                                    }
+                                }
+                                
+                                {move || game_state.get()
+                                    .filter(|state| state.is_complete())
+                                    .map(|state| view! {
+                                        {scenario.map(|scenario| view! { <ScenarioCard scenario=scenario /> })}
+                                        <HistoryScreen game_state=state />
+                                    })}
+                                
+                                {move || run_complete.get().then(|| view! { <LoopBack on_loop=loop_back /> })}
+                            </div>
+                            
+                            <aside class="game-sidebar">
+                                <StoryTree game_state=state.clone() tree=tree layout=layout />
+                                <KeyboardLegend options=options />
+                                <VotingPanel path=node_path has_choice=has_choice />
+                                
+                                <WorldRadar axes=axes game_state=live_state />
+                                
+                                {move || view! {
+                                    <GemInventory
+                                        gems=live_state.with(|state| state.gems.clone())
+                                        remembered=memory.with(|memory| memory.remembered_gems().len())
+                                        loop_count=memory.with(|memory| memory.loop_count())
+                                    />
+                                }}
+                                
+                                <ControlPanel 
+                                    game_state=live_state
+                                    ending_count=ending_count
+                                    ending=ending
+                                    on_reset=reset_game
+                                    on_load=load_game
+                                />
+                            </aside>
+                        </div>
+                    }.into_view()
+                }
+                Ok(None) => view! { <NotFound/> }.into_view(),
+                Err(e) => view! {
+                    <div class="error">
+                        <p>{I18n::t(locale.get_untracked(), "app.error")} {e.to_string()}</p>
+                    </div>
+                }.into_view(),
+            })}
+        </Transition>
+    }
+}
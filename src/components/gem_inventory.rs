@@ -0,0 +1,39 @@
+use leptos::*;
+use crate::models::{CollectedGem, Locale};
+use crate::utils::I18n;
+
+#[component]
+pub fn GemInventory(
+    gems: Vec<CollectedGem>,
+    remembered: usize,
+    loop_count: usize,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+
+    view! {
+        <div class="gem-inventory">
+            <h3 class="panel-title">{I18n::t(locale, "gems.title")}</h3>
+            {(loop_count > 0).then(|| view! {
+                <p class="loop-memory">
+                    {I18n::format(locale, "gems.loop", &[("loop", &(loop_count + 1)), ("count", &remembered)])}
+                </p>
+            })}
+            {if gems.is_empty() {
+                view! {
+                    <p class="no-gems">{I18n::t(locale, "gems.none")}</p>
+                }.into_view()
+            } else {
+                view! {
+                    <ol class="gem-list">
+                        {gems.into_iter().map(|collected| view! {
+                            <li class="gem-item" title=collected.gem.description.clone()>
+                                <span class="gem-year">{collected.gem.year}</span>
+                                <span class="gem-name">{collected.gem.name.clone()}</span>
+                            </li>
+                        }).collect_view()}
+                    </ol>
+                }.into_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,65 @@
+use leptos::*;
+use crate::models::{GameState, Locale};
+use crate::services::compile_history;
+use crate::utils::{HistoryExporter, I18n};
+
+#[component]
+pub fn HistoryScreen(
+    game_state: GameState,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let history = create_resource(
+        move || (game_state.clone(), locale),
+        |(game_state, locale)| compile_history(game_state, locale),
+    );
+
+    view! {
+        <div class="history-screen">
+            <h2 class="history-title">"📜 " {I18n::t(locale, "history.title")}</h2>
+            <Suspense fallback=move || view! { <p>{I18n::t(locale, "history.compiling")}</p> }>
+                {move || history.get().map(|result| match result {
+                    Ok(history) => {
+                        let file_stem = I18n::format(locale, "history.file_stem", &[("path", &history.path)]);
+                        let markdown_href = HistoryExporter::data_url("text/markdown", &HistoryExporter::to_markdown(&history));
+                        let html_href = HistoryExporter::data_url("text/html", &HistoryExporter::to_html(&history));
+                        view! {
+                            <p class="history-summary">
+                                {I18n::format(locale, "history.summary", &[
+                                    ("path", &history.path),
+                                    ("gems", &history.gem_count()),
+                                    ("total", &history.entries.len()),
+                                ])}
+                            </p>
+                            <ol class="history-timeline">
+                                {history.entries.iter().map(|entry| view! {
+                                    <li class="history-entry">
+                                        <span class="history-year">
+                                            {HistoryExporter::year_label(entry, locale)}
+                                        </span>
+                                        <h4 class="history-entry-title">{entry.title.clone()}</h4>
+                                        {entry.gem.as_ref().map(|gem| view! {
+                                            <p class="history-gem">"💎 " {gem.name.clone()} ": " {gem.description.clone()}</p>
+                                        })}
+                                    </li>
+                                }).collect_view()}
+                            </ol>
+                            <div class="history-downloads">
+                                <a class="control-button" href=markdown_href download=format!("{}.md", file_stem)>
+                                    {I18n::t(locale, "history.download_markdown")}
+                                </a>
+                                <a class="control-button" href=html_href download=format!("{}.html", file_stem)>
+                                    {I18n::t(locale, "history.download_html")}
+                                </a>
+                            </div>
+                        }.into_view()
+                    }
+                    Err(e) => view! {
+                        <div class="error">
+                            <p>{I18n::t(locale, "app.error")} {e.to_string()}</p>
+                        </div>
+                    }.into_view(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,37 @@
+use leptos::*;
+use crate::models::{ChoiceOption, Locale};
+use crate::utils::{I18n, Shortcut};
+
+// Pressing space asks every streaming text on the page to show in full
+#[derive(Debug, Clone, Copy)]
+pub struct SkipText(pub Trigger);
+
+// Pressing Esc anywhere, even in a text field, moves focus to the control panel
+#[derive(Debug, Clone, Copy)]
+pub struct MenuFocus(pub Trigger);
+
+#[component]
+pub fn KeyboardLegend(
+    options: Vec<ChoiceOption>,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+
+    view! {
+        <section class="keyboard-legend" aria-labelledby="keyboard-legend-title">
+            <h3 class="panel-title" id="keyboard-legend-title">{I18n::t(locale, "keys.title")}</h3>
+            <dl class="legend-list">
+                {options.into_iter().filter_map(|option| {
+                    let shortcut = Shortcut::Choose(option.choice_type()?);
+                    Some(view! {
+                        <dt><kbd>{shortcut.aria_keys()}</kbd></dt>
+                        <dd>{option.label}</dd>
+                    })
+                }).collect_view()}
+                <dt><kbd>{I18n::t(locale, "keys.space")}</kbd></dt>
+                <dd>{I18n::t(locale, "keys.skip")}</dd>
+                <dt><kbd>"Esc"</kbd></dt>
+                <dd>{I18n::t(locale, "keys.menu")}</dd>
+            </dl>
+        </section>
+    }
+}
@@ -0,0 +1,66 @@
+use leptos::*;
+use crate::models::Locale;
+use crate::utils::I18n;
+use std::rc::Rc;
+use std::time::Duration;
+
+pub const LOOP_BACK_SECONDS: u32 = 20;
+
+#[component]
+pub fn LoopBack(
+    on_loop: impl Fn() + 'static,
+    // How long the ending stays up before the wheel turns
+    #[prop(default = LOOP_BACK_SECONDS)]
+    seconds: u32,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let (remaining, set_remaining) = create_signal(seconds.max(1));
+    let (paused, set_paused) = create_signal(false);
+    let on_loop = Rc::new(on_loop);
+
+    // Timers only run in the browser, so start the countdown after mount
+    let tick_loop = on_loop.clone();
+    create_effect(move |_| {
+        let tick_loop = tick_loop.clone();
+        if let Ok(handle) = set_interval_with_handle(
+            move || {
+                let current = remaining.get_untracked();
+                if paused.get_untracked() || current == 0 {
+                    return;
+                }
+                let left = current - 1;
+                set_remaining.set(left);
+                if left == 0 {
+                    tick_loop();
+                }
+            },
+            Duration::from_secs(1),
+        ) {
+            on_cleanup(move || handle.clear());
+        }
+    });
+
+    view! {
+        <div class="loop-back">
+            <p class="loop-back-text">
+                {move || if paused.get() {
+                    I18n::t(locale, "loop_back.paused").to_string()
+                } else {
+                    I18n::format(locale, "loop_back.countdown", &[("seconds", &remaining.get())])
+                }}
+            </p>
+            <button
+                class="control-button loop-now-button"
+                on:click=move |_| on_loop()
+            >
+                {I18n::t(locale, "loop_back.now")}
+            </button>
+            <button
+                class="control-button loop-pause-button"
+                on:click=move |_| set_paused.update(|p| *p = !*p)
+            >
+                {move || I18n::t(locale, if paused.get() { "loop_back.resume" } else { "loop_back.stay" })}
+            </button>
+        </div>
+    }
+}
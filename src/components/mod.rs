@@ -3,9 +3,35 @@ pub mod story_display;
 pub mod choice_buttons;
 pub mod story_tree;
 pub mod control_panel;
+pub mod game_page;
+pub mod not_found;
+pub mod gem_inventory;
+pub mod history_screen;
+pub mod loop_back;
+pub mod static_site;
+pub mod keyboard_legend;
+pub mod tree_svg;
+pub mod scenario_card;
+pub mod world_radar;
+pub mod voting_panel;
+pub mod vote_page;
+pub mod choice_share;
 
 pub use app::*;
 pub use story_display::*;
 pub use choice_buttons::*;
 pub use story_tree::*;
-pub use control_panel::*;
\ No newline at end of file
+pub use control_panel::*;
+pub use game_page::*;
+pub use not_found::*;
+pub use gem_inventory::*;
+pub use history_screen::*;
+pub use loop_back::*;
+pub use static_site::*;
+pub use keyboard_legend::*;
+pub use tree_svg::*;
+pub use scenario_card::*;
+pub use world_radar::*;
+pub use voting_panel::*;
+pub use vote_page::*;
+pub use choice_share::*;
@@ -0,0 +1,28 @@
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::*;
+use crate::models::Locale;
+use crate::utils::I18n;
+use crate::components::{play_href, use_url_locale};
+
+#[component]
+pub fn NotFound() -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    {
+        if let Some(response) = use_context::<leptos_axum::ResponseOptions>() {
+            response.set_status(axum::http::StatusCode::NOT_FOUND);
+        }
+    }
+
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let home = play_href("", use_url_locale().get_untracked());
+
+    view! {
+        <Title text=format!("404 | {}", I18n::t(locale, "app.title"))/>
+        <div class="not-found">
+            <h2>"404"</h2>
+            <p>{I18n::t(locale, "not_found.message")}</p>
+            <A href=home>{I18n::t(locale, "not_found.back")}</A>
+        </div>
+    }
+}
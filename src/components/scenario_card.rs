@@ -0,0 +1,21 @@
+use leptos::*;
+use crate::models::{Locale, Scenario};
+use crate::utils::I18n;
+
+#[component]
+pub fn ScenarioCard(
+    scenario: Scenario,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+
+    view! {
+        <section class=format!("scenario-card scenario-{}", scenario.key())>
+            <h3 class="scenario-title">"🔭 " {I18n::t(locale, "scenario.title")}</h3>
+            <p class="scenario-name">
+                {I18n::format(locale, "scenario.led_to", &[("name", &scenario.name(locale))])}
+            </p>
+            <p class="scenario-about">{scenario.description(locale)}</p>
+            <p class="scenario-book">{I18n::t(locale, "scenario.book")}</p>
+        </section>
+    }
+}
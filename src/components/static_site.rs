@@ -0,0 +1,138 @@
+use leptos::*;
+use crate::models::{GameState, Locale, StoryNode};
+use crate::utils::I18n;
+use crate::components::{ChoiceButtons, ScenarioCard, StoryDisplay, StoryTree, WorldRadar};
+
+// Provided while pre-rendering pages for a plain file server: there is no
+// script, so text is shown in full and choices are links between pages
+#[derive(Debug, Clone, Copy)]
+pub struct StaticRender;
+
+pub fn is_static_render() -> bool {
+    use_context::<StaticRender>().is_some()
+}
+
+// Pages sit side by side in one directory, so links stay relative
+pub fn static_href(path: &str) -> String {
+    if path.is_empty() {
+        "index.html".to_string()
+    } else {
+        format!("{}.html", path)
+    }
+}
+
+#[component]
+pub fn StaticPage(node: StoryNode) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let state = node.game_state().unwrap_or_else(|| GameState::with_max_depth(node.max_depth));
+    let is_complete = state.is_complete();
+    let tree = node.tree.clone();
+    let layout = node.layout.clone();
+    let axes = node.axes.clone();
+    let scenario = node.story.as_ref().and_then(|story| story.scenario).filter(|_| is_complete);
+
+    view! {
+        <div class="app-container">
+            <header class="app-header">
+                <h1><a href=static_href("")>{I18n::t(locale, "app.title")}</a></h1>
+                <p>{I18n::t(locale, "app.tagline")}</p>
+            </header>
+
+            <main class="app-main">
+                <div class="game-container">
+                    <div class="game-content">
+                        <StoryDisplay
+                            node=node.clone()
+                            gem_collected=Signal::derive(|| true)
+                            on_collect_gem=|_| {}
+                        />
+                        <ChoiceButtons
+                            node=node
+                            game_state=state.clone()
+                            explored=Vec::new()
+                            on_choice=|_| {}
+                        />
+                        {scenario.map(|scenario| view! { <ScenarioCard scenario=scenario /> })}
+                        {is_complete.then(|| view! {
+                            <div class="loop-back">
+                                <a class="control-button loop-now-button" href=static_href("")>
+                                    {I18n::t(locale, "loop_back.now")}
+                                </a>
+                            </div>
+                        })}
+                    </div>
+
+                    <aside class="game-sidebar">
+                        <StoryTree game_state=state.clone() tree=tree layout=layout />
+                        <WorldRadar axes=axes game_state=Signal::derive(move || state.clone()) />
+                    </aside>
+                </div>
+            </main>
+        </div>
+    }
+}
+
+// Written once at the root; pages in a language folder reach it one level up
+#[cfg(feature = "ssr")]
+const STYLESHEET: &str = "style.css";
+
+#[cfg(feature = "ssr")]
+pub fn build_static_site(
+    story: &crate::services::SharedStoryData,
+    out_dir: &std::path::Path,
+) -> std::io::Result<usize> {
+    use crate::utils::escape_html;
+
+    std::fs::create_dir_all(out_dir)?;
+    std::fs::write(out_dir.join(STYLESHEET), include_str!("../style/static_site.css"))?;
+
+    let mut pages = 0;
+    for locale in story.locales() {
+        // The default language sits at the root, the others in a folder named by code
+        let (dir, stylesheet) = if locale == Locale::default() {
+            (out_dir.to_path_buf(), STYLESHEET.to_string())
+        } else {
+            (out_dir.join(locale.code()), format!("../{}", STYLESHEET))
+        };
+        std::fs::create_dir_all(&dir)?;
+
+        let pack = story.pack(locale);
+        let navigator = pack.navigator();
+        for path in navigator.reachable_paths() {
+            let Some(game_state) = navigator.state_for_path(&path) else {
+                continue;
+            };
+            let node = navigator.get_node(&game_state);
+            let title = node.story.as_ref()
+                .map(|story| story.title.clone())
+                .unwrap_or_else(|| I18n::t(locale, "app.gate").to_string());
+            let description = if path.is_empty() {
+                format!("{} · {}", title, I18n::t(locale, "app.tagline"))
+            } else {
+                I18n::format(locale, "app.description_path", &[("title", &title), ("path", &path)])
+            };
+
+            let body = leptos::ssr::render_to_string(move || {
+                provide_context(Signal::derive(move || locale));
+                provide_context(StaticRender);
+                view! { <StaticPage node=node.clone()/> }
+            });
+            let html = format!(
+                "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+                 <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+                 <title>{} | {}</title>\n<meta name=\"description\" content=\"{}\">\n\
+                 <link rel=\"stylesheet\" href=\"{}\">\n</head>\n\
+                 <body>\n{}\n</body>\n</html>\n",
+                locale.code(),
+                escape_html(&title),
+                escape_html(I18n::t(locale, "app.title")),
+                escape_html(&description),
+                stylesheet,
+                body
+            );
+            std::fs::write(dir.join(static_href(&path)), html)?;
+            pages += 1;
+        }
+    }
+    Ok(pages)
+}
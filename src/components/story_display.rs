@@ -1,16 +1,23 @@
 use leptos::*;
-use crate::models::{StoryData, GameState};
-use crate::services::PathNavigator;
+use std::rc::Rc;
+use gloo_timers::future::TimeoutFuture;
+use crate::models::{Locale, StoryNode, TextSpeed, TimeGem};
+use crate::utils::{I18n, Shortcut, TextStreamer};
+use crate::components::{is_static_render, SkipText};
+
+const PAUSE_POLL_MS: u32 = 100;
 
 #[component]
 pub fn StoryDisplay(
-    story_data: StoryData,
-    game_state: GameState,
+    node: StoryNode,
+    #[prop(into)] gem_collected: Signal<bool>,
+    on_collect_gem: impl Fn(TimeGem) + 'static,
 ) -> impl IntoView {
-    let navigator = PathNavigator::new(&story_data);
-    
-    let current_story = navigator.get_current_story(&game_state);
-    let current_choice = navigator.get_current_choice(&game_state);
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let current_story = node.story;
+    let current_choice = node.choice;
+    let room_gem = current_story.as_ref().and_then(|story| story.gem.clone());
+    let on_collect_gem = Rc::new(on_collect_gem);
     
     view! {
         <div class="story-display">
@@ -26,11 +33,45 @@ pub fn StoryDisplay(
             } else {
                 view! {
                     <div class="story-content">
-                        <h2 class="story-title">"故事加载中..."</h2>
+                        <h2 class="story-title">{I18n::t(locale, "story.loading")}</h2>
                     </div>
                 }.into_view()
             }}
             
+            {node.intro.map(|intro| view! {
+                <div class="story-intro">
+                    <h3 class="intro-title">{intro.title}</h3>
+                    <p class="intro-story">{intro.story}</p>
+                </div>
+            })}
+            
+            {room_gem.map(|gem| {
+                view! {
+                    <div class="time-gem">
+                        {move || if gem_collected.get() {
+                            view! {
+                                <div class="gem-details">
+                                    <h4 class="gem-name">"💎 " {gem.name.clone()}</h4>
+                                    <p class="gem-year">{I18n::format(locale, "history.year", &[("year", &gem.year)])}</p>
+                                    <p class="gem-description">{gem.description.clone()}</p>
+                                </div>
+                            }.into_view()
+                        } else {
+                            let gem = gem.clone();
+                            let on_collect_gem = on_collect_gem.clone();
+                            view! {
+                                <button
+                                    class="gem-button"
+                                    on:click=move |_| on_collect_gem(gem.clone())
+                                >
+                                    {I18n::t(locale, "story.collect_gem")}
+                                </button>
+                            }.into_view()
+                        }}
+                    </div>
+                }
+            })}
+            
             {if let Some(choice) = current_choice {
                 view! {
                     <div class="choice-prompt">
@@ -38,8 +79,7 @@ pub fn StoryDisplay(
                         <p class="choice-story">{choice.story.clone()}</p>
                     </div>
                 }.into_view()
-            } else if game_state.is_complete() {
-                let final_story = navigator.get_final_story();
+            } else if let Some(final_story) = node.final_story {
                 view! {
                     <div class="final-story">
                         <h2 class="final-title">{final_story.title.clone()}</h2>
@@ -51,7 +91,7 @@ pub fn StoryDisplay(
             } else {
                 view! {
                     <div class="empty-choice">
-                        <p>"准备开始新的故事..."</p>
+                        <p>{I18n::t(locale, "story.empty")}</p>
                     </div>
                 }.into_view()
             }}
@@ -59,42 +99,110 @@ pub fn StoryDisplay(
     }
 }
 
+// Some players get motion sickness from animated text; honor the OS setting
+fn prefers_reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
 #[component]
 pub fn StreamingText(text: String) -> impl IntoView {
-    let (displayed_text, set_displayed_text) = create_signal(String::new());
-    let (is_streaming, set_is_streaming) = create_signal(true);
-    
+    if is_static_render() {
+        return view! {
+            <div class="streaming-text">
+                <p>{text}</p>
+            </div>
+        }.into_view();
+    }
+
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let speed = expect_context::<RwSignal<TextSpeed>>();
+    let chunks = TextStreamer::default().smart_chunks(&text);
+    let chunk_count = chunks.len();
+    // The server renders the whole text, so readers without JS still get the story
+    let (revealed, set_revealed) = create_signal(chunk_count);
+    let (paused, set_paused) = create_signal(false);
+    let is_streaming = Signal::derive(move || revealed.get() < chunk_count);
+    let skip = move || set_revealed.set(chunk_count);
+
+    if let Some(SkipText(trigger)) = use_context::<SkipText>() {
+        create_effect(move |prev: Option<()>| {
+            trigger.track();
+            if prev.is_some() {
+                skip();
+            }
+        });
+    }
+
+    // Timers only run in the browser, so streaming starts after mount
+    let timer_chunks = chunks.clone();
     create_effect(move |_| {
-        let text_clone = text.clone();
+        if prefers_reduced_motion() {
+            return;
+        }
+        set_revealed.set(0);
+        let chunks = timer_chunks.clone();
         spawn_local(async move {
-            set_displayed_text.set(String::new());
-            set_is_streaming.set(true);
-            
-            let chars: Vec<char> = text_clone.chars().collect();
-            for (i, _) in chars.iter().enumerate() {
-                let partial_text: String = chars[0..=i].iter().collect();
-                set_displayed_text.set(partial_text);
-                
-                // Sleep for streaming effect
-                gloo_timers::future::TimeoutFuture::new(50).await;
+            for chunk in chunks {
+                // Wait out a pause; stop once skipped or unmounted
+                let speed = loop {
+                    match (revealed.try_get_untracked(), paused.try_get_untracked(), speed.try_get_untracked()) {
+                        (Some(shown), _, _) if shown >= chunk_count => return,
+                        (Some(_), Some(true), Some(_)) => TimeoutFuture::new(PAUSE_POLL_MS).await,
+                        (Some(_), Some(false), Some(speed)) => break speed,
+                        _ => return,
+                    }
+                };
+                if speed == TextSpeed::Instant {
+                    skip();
+                    return;
+                }
+                set_revealed.update(|shown| *shown += 1);
+                let delay = TextStreamer::from_speed(speed).chunk_delay(&chunk);
+                TimeoutFuture::new(delay.as_millis() as u32).await;
             }
-            
-            set_is_streaming.set(false);
         });
     });
     
     view! {
-        <div class="streaming-text">
-            <p>{move || displayed_text.get()}</p>
-            {move || {
-                if is_streaming.get() {
-                    view! {
-                        <span class="cursor">"|"</span>
-                    }.into_view()
-                } else {
-                    view! {}.into_view()
+        <div class="streaming-text" aria-busy=move || is_streaming.get().to_string()>
+            <p
+                class="streaming-body"
+                tabindex="0"
+                title=I18n::t(locale, "story.skip_hint")
+                on:click=move |_| skip()
+                on:keydown=move |ev| {
+                    if matches!(ev.key().as_str(), "Enter" | " ") {
+                        ev.prevent_default();
+                        skip();
+                    }
                 }
-            }}
+            >
+                {chunks.into_iter().enumerate().map(|(i, chunk)| view! {
+                    <span style:display=move || if i < revealed.get() { "inline" } else { "none" }>{chunk}</span>
+                }).collect_view()}
+                {move || is_streaming.get().then(|| view! { <span class="cursor">"|"</span> })}
+            </p>
+            {move || is_streaming.get().then(|| view! {
+                <div class="streaming-controls">
+                    <button
+                        class="control-button pause-button"
+                        on:click=move |_| set_paused.update(|p| *p = !*p)
+                    >
+                        {move || I18n::t(locale, if paused.get() { "story.resume" } else { "story.pause" })}
+                    </button>
+                    <button
+                        class="control-button skip-button"
+                        aria-keyshortcuts=Shortcut::SkipText.aria_keys()
+                        on:click=move |_| skip()
+                    >
+                        {I18n::t(locale, "story.skip")}
+                    </button>
+                </div>
+            })}
         </div>
-    }
-}
\ No newline at end of file
+    }.into_view()
+}
@@ -1,26 +1,27 @@
 use leptos::*;
-use crate::models::{StoryData, GameState};
-use crate::services::PathNavigator;
+use crate::models::{GameState, Locale};
+use crate::utils::{I18n, TreeLayout};
+use crate::components::StoryTreeSvg;
 
 #[component]
 pub fn StoryTree(
-    story_data: StoryData,
     game_state: GameState,
+    tree: String,
+    layout: TreeLayout,
 ) -> impl IntoView {
-    let navigator = PathNavigator::new(&story_data);
-    let tree_text = navigator.generate_tree_visualization(&game_state);
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
     
     view! {
         <div class="story-tree">
-            <h3 class="tree-title">"故事路径"</h3>
+            <h3 class="tree-title">{I18n::t(locale, "story_tree.title")}</h3>
             <div class="tree-content">
-                <pre class="tree-text">{tree_text}</pre>
+                <StoryTreeSvg layout=layout tree=tree />
             </div>
             
             <div class="path-info">
-                <p><strong>"当前层级: "</strong> {game_state.get_level() + 1} "/6"</p>
-                <p><strong>"选择路径: "</strong> {game_state.get_path()}</p>
-                <p><strong>"进度: "</strong> {format!("{:.1}%", (game_state.get_level() as f32 / 6.0) * 100.0)}</p>
+                <p><strong>{I18n::t(locale, "story_tree.level")}</strong> {game_state.get_level() + 1} "/" {game_state.get_max_depth()}</p>
+                <p><strong>{I18n::t(locale, "story_tree.path")}</strong> {game_state.get_path().to_string()}</p>
+                <p><strong>{I18n::t(locale, "story_tree.progress")}</strong> {format!("{:.1}%", (game_state.get_level() as f32 / game_state.get_max_depth().max(1) as f32) * 100.0)}</p>
             </div>
         </div>
     }
@@ -0,0 +1,126 @@
+use leptos::*;
+use crate::models::Locale;
+use crate::services::get_story_node;
+use crate::utils::{I18n, NodeState, TreeLayout, NODE_RADIUS};
+use crate::components::is_static_render;
+
+#[component]
+pub fn StoryTreeSvg(
+    layout: TreeLayout,
+    // The same tree as text, read out by screen readers
+    tree: String,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let static_render = is_static_render();
+    let (preview, set_preview) = create_signal(None::<String>);
+    let (width, height) = (layout.width, layout.height);
+
+    let edges = layout.edges.iter().map(|edge| {
+        let (from, to) = (&layout.nodes[edge.from], &layout.nodes[edge.to]);
+        view! {
+            <line
+                x1=from.x y1=from.y x2=to.x y2=to.y
+                stroke=if edge.on_path { "#f08c00" } else { "#ced4da" }
+                stroke-width=if edge.on_path { 3 } else { 1 }
+            />
+        }
+    }).collect_view();
+
+    let nodes = layout.nodes.into_iter().map(|node| {
+        // Rooms already passed can be reread; the rest is still ahead
+        let rereadable = !static_render && node.state == NodeState::Visited;
+        let path = node.path.clone();
+        let open = move || set_preview.set(Some(path.clone()));
+        let open_by_key = open.clone();
+        view! {
+            <g
+                class=format!("tree-node {}", node.state.class())
+                role=rereadable.then_some("button")
+                tabindex=rereadable.then_some("0")
+                aria-label=node.title.clone()
+                on:click=move |_| if rereadable { open() }
+                on:keydown=move |ev| {
+                    if rereadable && matches!(ev.key().as_str(), "Enter" | " ") {
+                        ev.prevent_default();
+                        open_by_key();
+                    }
+                }
+            >
+                <title>{node.title.clone()}</title>
+                <circle
+                    cx=node.x cy=node.y r=NODE_RADIUS
+                    fill=node.fill().to_string()
+                    stroke=node.stroke().to_string()
+                    stroke-width=node.stroke_width()
+                    stroke-dasharray=node.dashed().then_some("4 3")
+                />
+                <text x=node.x y=node.y text-anchor="middle" dominant-baseline="central" font-size="12" fill=node.text_color().to_string()>
+                    {node.label.clone()}
+                </text>
+                {node.folded.then(|| view! {
+                    <text x=node.x y=node.y + NODE_RADIUS + 12.0 text-anchor="middle" font-size="12">"…"</text>
+                })}
+            </g>
+        }
+    }).collect_view();
+
+    view! {
+        <div class="tree-svg">
+            <svg
+                width=width
+                height=height
+                viewBox=format!("0 0 {} {}", width, height)
+                font-family="sans-serif"
+                role="group"
+                aria-label=I18n::t(locale, "story_tree.title")
+            >
+                <desc>{tree}</desc>
+                {edges}
+                {nodes}
+            </svg>
+            <p class="tree-legend">
+                <span class="legend-visited">"● " {I18n::t(locale, "story_tree.visited")}</span>
+                " "
+                <span class="legend-current">"◉ " {I18n::t(locale, "story_tree.current")}</span>
+                " "
+                <span class="legend-unexplored">"○ " {I18n::t(locale, "story_tree.unexplored")}</span>
+            </p>
+            {(!static_render).then(|| view! {
+                <p class="tree-hint">{I18n::t(locale, "story_tree.reread_hint")}</p>
+                <TreePreview path=preview on_close=move || set_preview.set(None)/>
+            })}
+        </div>
+    }
+}
+
+// A read-only look back at a room on the path; choices stay where the player is
+#[component]
+fn TreePreview(
+    path: ReadSignal<Option<String>>,
+    on_close: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let preview_node = create_resource(
+        move || path.get(),
+        move |path| async move {
+            match path {
+                Some(path) => get_story_node(path, locale).await.ok().flatten(),
+                None => None,
+            }
+        },
+    );
+
+    view! {
+        <Transition fallback=|| ()>
+            {move || preview_node.get().flatten().and_then(|node| node.story).map(|story| view! {
+                <section class="tree-preview" aria-live="polite">
+                    <h4 class="preview-title">{I18n::t(locale, "story_tree.preview")} " · " {story.title}</h4>
+                    <p class="preview-text">{story.story}</p>
+                    <button class="control-button" on:click=move |_| on_close()>
+                        {I18n::t(locale, "story_tree.close")}
+                    </button>
+                </section>
+            })}
+        </Transition>
+    }
+}
@@ -0,0 +1,139 @@
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::*;
+use crate::models::{normalize_join_code, Locale, VotePhase, VoteRequest, VoteRoomView};
+use crate::utils::I18n;
+use crate::components::{phase_message, socket_url, VoteConnection, VoteTally};
+
+// Where the audience types the code off the presenter's screen
+#[component]
+pub fn VoteJoinPage() -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>();
+    let code = create_rw_signal(String::new());
+    let invalid = create_rw_signal(false);
+    let navigate = use_navigate();
+
+    let join = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        match normalize_join_code(&code.get_untracked()) {
+            Some(code) => navigate(&format!("/vote/{}", code), Default::default()),
+            None => invalid.set(true),
+        }
+    };
+
+    view! {
+        <Title text=move || I18n::t(locale.get(), "vote.join_title")/>
+        <form class="vote-join-page" on:submit=join>
+            <h2>{move || I18n::t(locale.get(), "vote.join_title")}</h2>
+            <input
+                class="vote-code-input"
+                type="text"
+                autocomplete="off"
+                autocapitalize="characters"
+                placeholder=move || I18n::t(locale.get(), "vote.code")
+                prop:value=code
+                on:input=move |ev| {
+                    invalid.set(false);
+                    code.set(event_target_value(&ev));
+                }
+            />
+            <button class="choice-button" type="submit">{move || I18n::t(locale.get(), "vote.join")}</button>
+            {move || invalid.get().then(|| view! {
+                <p class="vote-error">{I18n::t(locale.get(), "vote.unknown_room")}</p>
+            })}
+        </form>
+    }
+}
+
+// An audience member's phone: one big button per option while a vote is open
+#[component]
+pub fn VotePage() -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>();
+    let params = use_params_map();
+    let code = move || params.with(|p| p.get("code").and_then(|code| normalize_join_code(code)));
+
+    let room = create_rw_signal(None::<VoteRoomView>);
+    let closed = create_rw_signal(false);
+    let my_vote = create_rw_signal(None::<char>);
+    let connection = store_value(None::<VoteConnection>);
+
+    create_effect(move |_| {
+        let Some(code) = code() else {
+            closed.set(true);
+            return;
+        };
+        let url = socket_url(&format!("/ws/vote/{}", code));
+        connection.set_value(VoteConnection::open(&url, room, move || closed.set(true)));
+        on_cleanup(move || connection.set_value(None));
+    });
+
+    // A new vote starts with a clean slate
+    create_effect(move |was_open: Option<bool>| {
+        let open = room.with(|room| room.as_ref().is_some_and(VoteRoomView::is_open));
+        if open && was_open != Some(true) {
+            my_vote.set(None);
+        }
+        open
+    });
+
+    let vote = move |key: char| {
+        my_vote.set(Some(key));
+        connection.with_value(|connection| {
+            if let Some(connection) = connection {
+                connection.send(&VoteRequest::Vote { key });
+            }
+        });
+    };
+
+    view! {
+        <Title text=move || I18n::t(locale.get(), "vote.title")/>
+        <div class="vote-page">
+            {move || {
+                let locale = locale.get();
+                match room.get() {
+                    // The server turns unknown codes away before the socket opens
+                    None if closed.get() => view! {
+                        <p class="vote-error">{I18n::t(locale, "vote.unknown_room")}</p>
+                        <A href="/vote">{I18n::t(locale, "vote.join_title")}</A>
+                    }.into_view(),
+                    None => view! { <p class="loading">{I18n::t(locale, "vote.connecting")}</p> }.into_view(),
+                    Some(view) if closed.get() || view.phase == VotePhase::Ended => view! {
+                        <p class="vote-status">{I18n::t(locale, "vote.ended")}</p>
+                    }.into_view(),
+                    Some(view) => {
+                        let open = view.is_open();
+                        let status = phase_message(locale, &view);
+                        let options = view.choice.as_ref()
+                            .map(|choice| choice.options.clone())
+                            .unwrap_or_default();
+                        view! {
+                            <p class="vote-code">{view.code.clone()}</p>
+                            {view.choice.as_ref().map(|choice| view! { <h2 class="vote-question">{choice.title.clone()}</h2> })}
+                            <p class="vote-status">{status}</p>
+                            {open.then(|| view! {
+                                <div class="button-container" data-options=options.len()>
+                                    {options.into_iter().map(|option| {
+                                        let key = option.key;
+                                        view! {
+                                            <button
+                                                class=format!("choice-button {}-button", option.color)
+                                                class:chosen=move || my_vote.get() == Some(key)
+                                                on:click=move |_| vote(key)
+                                            >
+                                                <span class="button-text">{option.label}</span>
+                                            </button>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                            })}
+                            {move || my_vote.get().filter(|_| open).map(|_| view! {
+                                <p class="vote-mine">{I18n::t(locale, "vote.your_vote")}</p>
+                            })}
+                            {(open || matches!(view.phase, VotePhase::Decided { .. })).then(|| view! { <VoteTally room=view.clone() /> })}
+                        }.into_view()
+                    }
+                }
+            }}
+        </div>
+    }
+}
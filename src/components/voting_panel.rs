@@ -0,0 +1,200 @@
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+use crate::models::{Locale, VotePhase, VoteRequest, VoteRoomView, DEFAULT_VOTE_SECONDS};
+use crate::utils::I18n;
+
+// `/ws/vote...` on whichever host served the page, over TLS if the page was
+pub fn socket_url(path: &str) -> String {
+    let location = window().location();
+    let scheme = if location.protocol().as_deref() == Ok("https:") { "wss" } else { "ws" };
+    format!("{}://{}{}", scheme, location.host().unwrap_or_default(), path)
+}
+
+// An open voting socket that writes every room view it receives into `room`;
+// dropping it closes the socket
+pub struct VoteConnection {
+    socket: web_sys::WebSocket,
+    _on_message: Closure<dyn Fn(web_sys::MessageEvent)>,
+    _on_close: Closure<dyn Fn()>,
+}
+
+impl VoteConnection {
+    pub fn open(url: &str, room: RwSignal<Option<VoteRoomView>>, on_close: impl Fn() + 'static) -> Option<Self> {
+        let socket = web_sys::WebSocket::new(url).ok()?;
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |ev: web_sys::MessageEvent| {
+            let Some(text) = ev.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<VoteRoomView>(&text) {
+                Ok(view) => room.set(Some(view)),
+                Err(e) => logging::error!("Bad voting room update: {}", e),
+            }
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let on_close = Closure::<dyn Fn()>::new(on_close);
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        Some(Self {
+            socket,
+            _on_message: on_message,
+            _on_close: on_close,
+        })
+    }
+
+    pub fn send(&self, request: &VoteRequest) {
+        if let Ok(text) = serde_json::to_string(request) {
+            if let Err(e) = self.socket.send_with_str(&text) {
+                logging::error!("Failed to send vote request: {:?}", e);
+            }
+        }
+    }
+}
+
+impl Drop for VoteConnection {
+    fn drop(&mut self) {
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+// The presenter's room outlives page navigation, so like the gem inventory it lives above the router
+#[derive(Clone, Copy)]
+pub struct VotingHost {
+    pub room: RwSignal<Option<VoteRoomView>>,
+    connection: StoredValue<Option<VoteConnection>>,
+}
+
+impl Default for VotingHost {
+    fn default() -> Self {
+        Self {
+            room: create_rw_signal(None),
+            connection: store_value(None),
+        }
+    }
+}
+
+impl VotingHost {
+    pub fn start(&self, locale: Locale) {
+        let room = self.room;
+        let url = socket_url(&format!("/ws/vote?lang={}", locale.code()));
+        self.connection.set_value(VoteConnection::open(&url, room, move || room.set(None)));
+    }
+
+    pub fn send(&self, request: VoteRequest) {
+        self.connection.with_value(|connection| {
+            if let Some(connection) = connection {
+                connection.send(&request);
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.connection.set_value(None);
+        self.room.set(None);
+    }
+}
+
+// Tallies as bars, in the order the choice lists its options
+#[component]
+pub fn VoteTally(room: VoteRoomView) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let total = room.total_votes();
+    let Some(choice) = room.choice else {
+        return ().into_view();
+    };
+
+    view! {
+        <ul class="vote-tally">
+            {room.tallies.into_iter().filter_map(|(key, count)| {
+                let option = choice.options.iter().find(|option| option.key == key)?;
+                let percent = (count * 100).checked_div(total).unwrap_or(0);
+                Some(view! {
+                    <li class=format!("vote-tally-row {}-tally", option.color)>
+                        <span class="vote-tally-label">{option.label.clone()}</span>
+                        <span class="vote-tally-bar" style=format!("width: {}%", percent)></span>
+                        <span class="vote-tally-count">{I18n::format(locale, "vote.count", &[("count", &count)])}</span>
+                    </li>
+                })
+            }).collect_view()}
+        </ul>
+    }.into_view()
+}
+
+// What the room did last, shared by the presenter and the audience
+pub fn phase_message(locale: Locale, view: &VoteRoomView) -> String {
+    match &view.phase {
+        VotePhase::Waiting => I18n::t(locale, "vote.waiting").to_string(),
+        VotePhase::Open { seconds_left } => I18n::format(locale, "vote.seconds_left", &[("seconds", seconds_left)]),
+        VotePhase::Decided { key: None } => I18n::t(locale, "vote.no_votes").to_string(),
+        VotePhase::Decided { key: Some(key) } => {
+            let label = view.choice.as_ref()
+                .and_then(|choice| choice.options.iter().find(|option| option.key == *key))
+                .map_or_else(|| key.to_string(), |option| option.label.clone());
+            I18n::format(locale, "vote.decided", &[("option", &label)])
+        }
+        VotePhase::Ended => I18n::t(locale, "vote.ended").to_string(),
+    }
+}
+
+// The presenter's side of a voting room: open it, show the join code, then put each choice to a vote
+#[component]
+pub fn VotingPanel(
+    path: String,
+    has_choice: bool,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    let host = expect_context::<VotingHost>();
+    let path = store_value(path);
+
+    view! {
+        <div class="voting-panel">
+            <h3 class="panel-title">{I18n::t(locale, "vote.title")}</h3>
+            {move || match host.room.get() {
+                None => view! {
+                    <button class="control-button" on:click=move |_| host.start(locale)>
+                        {I18n::t(locale, "vote.start")}
+                    </button>
+                }.into_view(),
+                Some(view) => {
+                    let join_url = format!("/vote/{}", view.code);
+                    let open = view.is_open();
+                    let status = phase_message(locale, &view);
+                    view! {
+                        <p class="vote-join">
+                            {I18n::t(locale, "vote.join_at")} " "
+                            <a href=join_url.clone() target="_blank">{join_url.clone()}</a>
+                        </p>
+                        <p class="vote-code">{view.code.clone()}</p>
+                        <p class="vote-voters">{I18n::format(locale, "vote.voters", &[("count", &view.voters)])}</p>
+                        <p class="vote-status">{status}</p>
+                        {(open || matches!(view.phase, VotePhase::Decided { .. })).then(|| view! { <VoteTally room=view.clone() /> })}
+                        {if open {
+                            view! {
+                                <button class="control-button" on:click=move |_| host.send(VoteRequest::Close)>
+                                    {I18n::t(locale, "vote.close_now")}
+                                </button>
+                            }.into_view()
+                        } else if has_choice {
+                            view! {
+                                <button
+                                    class="control-button"
+                                    on:click=move |_| host.send(VoteRequest::Open {
+                                        path: path.get_value(),
+                                        seconds: DEFAULT_VOTE_SECONDS,
+                                    })
+                                >
+                                    {I18n::t(locale, "vote.open")}
+                                </button>
+                            }.into_view()
+                        } else {
+                            ().into_view()
+                        }}
+                        <button class="control-button" on:click=move |_| host.stop()>
+                            {I18n::t(locale, "vote.leave")}
+                        </button>
+                    }.into_view()
+                }
+            }}
+        </div>
+    }
+}
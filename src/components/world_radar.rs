@@ -0,0 +1,101 @@
+use leptos::*;
+use std::f64::consts::{FRAC_PI_2, TAU};
+use crate::models::{Axis, GameState, Locale};
+use crate::utils::I18n;
+
+const SIZE: f64 = 240.0;
+const RADIUS: f64 = 72.0;
+const LABEL_RADIUS: f64 = RADIUS + 18.0;
+
+// The first axis points up, the rest follow clockwise
+fn point(index: usize, count: usize, distance: f64) -> (f64, f64) {
+    let angle = TAU * index as f64 / count as f64 - FRAC_PI_2;
+    (SIZE / 2.0 + distance * angle.cos(), SIZE / 2.0 + distance * angle.sin())
+}
+
+fn polygon(count: usize, distance: impl Fn(usize) -> f64) -> String {
+    (0..count)
+        .map(|i| {
+            let (x, y) = point(i, count, distance(i));
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Where the player's world stands on every axis the story's choices move;
+// the dashed ring is where an untouched axis sits
+#[component]
+pub fn WorldRadar(
+    axes: Vec<Axis>,
+    #[prop(into)] game_state: Signal<GameState>,
+) -> impl IntoView {
+    let locale = expect_context::<Signal<Locale>>().get_untracked();
+    if axes.is_empty() {
+        return ().into_view();
+    }
+    let count = axes.len();
+    let axes = store_value(axes);
+    let values = create_memo(move |_| {
+        game_state.with(|state| axes.with_value(|axes| {
+            axes.iter().map(|axis| state.axis_value(&axis.name)).collect::<Vec<i32>>()
+        }))
+    });
+
+    let rings = [0.25, 0.5, 0.75, 1.0].into_iter().map(|ring| view! {
+        <polygon points=polygon(count, |_| RADIUS * ring) fill="none" stroke="#dee2e6" stroke-width="1"/>
+    }).collect_view();
+    let neutral = axes.with_value(|axes| polygon(count, |i| RADIUS * axes[i].fraction(0)));
+    let shape = move || values.with(|values| axes.with_value(|axes| {
+        polygon(count, |i| RADIUS * axes[i].fraction(values[i]))
+    }));
+    let spokes = axes.with_value(|axes| axes.iter().enumerate().map(|(i, axis)| {
+        let (x, y) = point(i, count, RADIUS);
+        let (label_x, label_y) = point(i, count, LABEL_RADIUS);
+        let anchor = if (label_x - SIZE / 2.0).abs() < 1.0 {
+            "middle"
+        } else if label_x < SIZE / 2.0 {
+            "end"
+        } else {
+            "start"
+        };
+        view! {
+            <line x1={SIZE / 2.0} y1={SIZE / 2.0} x2=x y2=y stroke="#dee2e6" stroke-width="1"/>
+            <text x=label_x y=label_y text-anchor=anchor dominant-baseline="central" font-size="11" fill="#495057">
+                {axis.label(locale)}
+            </text>
+        }
+    }).collect_view());
+
+    view! {
+        <div class="world-radar">
+            <h3 class="panel-title">{I18n::t(locale, "world.title")}</h3>
+            <svg
+                width=SIZE
+                height=SIZE
+                viewBox=format!("0 0 {} {}", SIZE, SIZE)
+                font-family="sans-serif"
+                role="img"
+                aria-label=I18n::t(locale, "world.title")
+            >
+                {rings}
+                {spokes}
+                <polygon points=neutral fill="none" stroke="#adb5bd" stroke-width="1" stroke-dasharray="4 3"/>
+                <polygon points=shape fill="#f08c00" fill-opacity="0.35" stroke="#f08c00" stroke-width="2"/>
+            </svg>
+            {move || if game_state.with(|state| state.world.is_empty()) {
+                view! { <p class="world-empty">{I18n::t(locale, "world.empty")}</p> }.into_view()
+            } else {
+                view! {
+                    <ul class="world-values">
+                        {values.with(|values| axes.with_value(|axes| {
+                            axes.iter().zip(values).map(|(axis, value)| view! {
+                                <li>{axis.label(locale)} ": " <span class="world-value">{format!("{:+}", value)}</span></li>
+                            }).collect_view()
+                        }))}
+                    </ul>
+                }.into_view()
+            }}
+        </div>
+    }.into_view()
+}
@@ -0,0 +1,18 @@
+// The game as a library: the server, the hydrated client and the CLIs all build on it
+pub mod components;
+pub mod models;
+pub mod services;
+pub mod utils;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "hydrate")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn hydrate() {
+    use components::App;
+    use leptos::*;
+
+    console_error_panic_hook::set_once();
+    mount_to_body(|| view! { <App/> });
+}
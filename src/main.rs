@@ -1,41 +1,92 @@
 use leptos::*;
-use leptos_axum::{generate_route_list, LeptosRoutes};
-use axum::{
-    routing::get,
-    Router,
-};
-use tower::ServiceBuilder;
-use tower_http::services::ServeDir;
-use std::env;
-
-mod models;
-mod components;
-mod services;
-mod utils;
-
-#[cfg(test)]
-mod tests;
-
-use components::App;
+use l3_story_game::components::App;
 
+#[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
+    use axum::{routing::get, Extension, Router};
+    use leptos_axum::{generate_route_list, LeptosRoutes};
+    use l3_story_game::components::build_static_site;
+    use l3_story_game::services::*;
+    use std::env;
+    use tower_http::services::ServeDir;
+
     console_error_panic_hook::set_once();
     
     let conf = get_configuration(None).await.unwrap();
     let leptos_options = conf.leptos_options;
     let addr = leptos_options.site_addr;
-    let routes = generate_route_list(|| view! { <App/> }).await;
+    let routes = generate_route_list(|| view! { <App/> });
+
+    // Load and validate the story once; server functions read it from context
+    let story_path = env::var("L3_STORY_FILE").unwrap_or_else(|_| "../docs/FM_STORY.toml".to_string());
+    let (story_data, report) = match StoryLoader::load_validated(&story_path) {
+        Ok(loaded) => loaded,
+        Err(StoryLoaderError::Invalid(report)) => {
+            for diagnostic in report.errors() {
+                tracing::error!("{}", diagnostic);
+            }
+            panic!("Story file {} failed validation", story_path);
+        }
+        Err(e) => panic!("Failed to load story data from {}: {}", story_path, e),
+    };
+    for warning in report.warnings() {
+        tracing::warn!("{}", warning);
+    }
+    // Other locales are optional packs next to the story file; gaps fall back to it
+    let translations = StoryLoader::load_translations(&story_path)
+        .unwrap_or_else(|e| panic!("Failed to load story translations for {}: {}", story_path, e));
+    for (locale, translation) in &translations {
+        tracing::info!("Loaded {} story pack", locale.code());
+        let missing = translation.untranslated(&story_data).len();
+        if missing > 0 {
+            tracing::warn!("The {} story pack leaves {} sections untranslated; `l3story validate` lists them", locale.code(), missing);
+        }
+    }
+    let story = SharedStoryData::new(story_data, translations);
 
-    // Build the Axum router
+    // `--static <dir>` pre-renders every page for a plain file server instead of serving
+    let args: Vec<String> = env::args().collect();
+    if let Some(i) = args.iter().position(|arg| arg == "--static") {
+        let out_dir = args.get(i + 1).map(String::as_str).unwrap_or("dist");
+        let pages = build_static_site(&story, std::path::Path::new(out_dir))
+            .unwrap_or_else(|e| panic!("Failed to write static site to {}: {}", out_dir, e));
+        println!("📄 Rendered {} pages to {}", pages, out_dir);
+        return;
+    }
+    let route_story = story.clone();
+
+    // How players choose at each node, counted across restarts
+    let choices_path = env::var("L3_CHOICES_DB").unwrap_or_else(|_| "choices.sqlite3".to_string());
+    let choices = ChoiceLog::open(&choices_path)
+        .unwrap_or_else(|e| panic!("Failed to open choice log {}: {}", choices_path, e));
+    let route_choices = choices.clone();
+
+    // Edits to the story file are validated and swapped in without a restart
+    let _watcher = StoryWatcher::spawn(story_path.clone().into(), story.clone())
+        .unwrap_or_else(|e| panic!("Failed to watch story file {}: {}", story_path, e));
+
+    // Build the Axum router; anything that isn't a route is a file from the site root
+    let site_root = leptos_options.site_root.clone();
     let app = Router::new()
-        .route("/api/*fn_name", get(leptos_axum::handle_server_fns))
-        .leptos_routes(&leptos_options, routes, || view! { <App/> })
-        .fallback(leptos_axum::file_and_error_handler(leptos_axum::handle_server_fns))
-        .layer(
-            ServiceBuilder::new()
-                .layer(tower_http::services::ServeDir::new(&leptos_options.site_root))
-        );
+        .route("/api/*fn_name", get(server_fn_handler).post(server_fn_handler))
+        .route("/events/story", get(story_events_handler))
+        .route("/ws/vote", get(vote_host_handler))
+        .route("/ws/vote/:code", get(vote_join_handler))
+        .leptos_routes_with_context(
+            &leptos_options,
+            routes,
+            move || {
+                provide_context(route_story.clone());
+                provide_context(route_choices.clone());
+            },
+            || view! { <App/> },
+        )
+        .fallback_service(ServeDir::new(site_root))
+        .layer(Extension(story))
+        .layer(Extension(choices))
+        .layer(Extension(VotingRooms::default()))
+        .with_state(leptos_options);
 
     // Run the server
     println!("🚀 L3 Story Game server starting at http://{}", addr);
@@ -55,10 +106,3 @@ pub fn main() {
     console_error_panic_hook::set_once();
     mount_to_body(|| view! { <App/> });
 }
-
-#[cfg(feature = "hydrate")]
-#[wasm_bindgen::prelude::wasm_bindgen]
-pub fn hydrate() {
-    console_error_panic_hook::set_once();
-    leptos::mount_to_body(|| view! { <App/> });
-}
\ No newline at end of file
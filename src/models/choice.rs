@@ -1,7 +1,13 @@
-#[derive(Debug, Clone, PartialEq)]
+use crate::models::Locale;
+use crate::utils::I18n;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ChoiceType {
     Red,
     Blue,
+    Other(char),
 }
 
 impl ChoiceType {
@@ -9,19 +15,45 @@ impl ChoiceType {
         match self {
             ChoiceType::Red => 'R',
             ChoiceType::Blue => 'B',
+            ChoiceType::Other(c) => *c,
         }
     }
-    
+
+    // Any uppercase letter or digit can key an option; R and B keep their names
     pub fn from_char(c: char) -> Option<Self> {
         match c {
             'R' => Some(ChoiceType::Red),
             'B' => Some(ChoiceType::Blue),
+            c if c.is_ascii_uppercase() || c.is_ascii_digit() => Some(ChoiceType::Other(c)),
             _ => None,
         }
     }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ChoiceType::Red => "🔴",
+            ChoiceType::Blue => "🔵",
+            ChoiceType::Other('G') => "🟢",
+            ChoiceType::Other('Y') => "🟡",
+            ChoiceType::Other('P') => "🟣",
+            ChoiceType::Other(_) => "🔸",
+        }
+    }
+
+    pub fn name(&self, locale: Locale) -> String {
+        let key = match self {
+            ChoiceType::Red => "choice.red",
+            ChoiceType::Blue => "choice.blue",
+            ChoiceType::Other('G') => "choice.green",
+            ChoiceType::Other('Y') => "choice.yellow",
+            ChoiceType::Other('P') => "choice.purple",
+            ChoiceType::Other(_) => "choice.other",
+        };
+        I18n::format(locale, key, &[("key", &self.as_char())])
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Choice {
     pub choice_type: ChoiceType,
     pub level: usize,
@@ -31,4 +63,23 @@ impl Choice {
     pub fn new(choice_type: ChoiceType, level: usize) -> Self {
         Self { choice_type, level }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChoiceOption {
+    pub key: char,
+    pub label: String,
+    #[serde(default)]
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    // How far picking this option moves each world-state axis
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub effects: BTreeMap<String, i32>,
+}
+
+impl ChoiceOption {
+    pub fn choice_type(&self) -> Option<ChoiceType> {
+        ChoiceType::from_char(self.key)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::ChoiceData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChoiceShare {
+    pub key: char,
+    pub label: String,
+    pub color: String,
+    pub count: u64,
+}
+
+// How every player who reached the choice at `path` decided there,
+// in the order the choice lists its options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChoiceStats {
+    pub path: String,
+    pub shares: Vec<ChoiceShare>,
+}
+
+impl ChoiceStats {
+    // Keys the story no longer offers are left out
+    pub fn new(path: &str, choice: &ChoiceData, counts: &HashMap<char, u64>) -> Self {
+        Self {
+            path: path.to_string(),
+            shares: choice
+                .options
+                .iter()
+                .map(|option| ChoiceShare {
+                    key: option.key,
+                    label: option.label.clone(),
+                    color: option.color.clone(),
+                    count: counts.get(&option.key).copied().unwrap_or(0),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.shares.iter().map(|share| share.count).sum()
+    }
+
+    pub fn share(&self, key: char) -> Option<&ChoiceShare> {
+        self.shares.iter().find(|share| share.key == key)
+    }
+
+    // Rounded to the nearest whole percent; `None` until somebody has chosen here
+    pub fn percent(&self, key: char) -> Option<u32> {
+        let total = self.total();
+        let count = self.share(key)?.count;
+        (total > 0).then(|| ((count * 100 + total / 2) / total) as u32)
+    }
+
+    // The option that leads from this choice straight to `path`
+    pub fn step_to(&self, path: &str) -> Option<char> {
+        let mut rest = path.strip_prefix(self.path.as_str())?.chars();
+        let key = rest.next()?;
+        (rest.next().is_none() && self.share(key).is_some()).then_some(key)
+    }
+}
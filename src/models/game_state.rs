@@ -1,10 +1,40 @@
-use crate::models::{Choice, ChoiceType};
+use crate::models::{Choice, ChoiceType, TimeGem};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Default)]
+pub const DEFAULT_MAX_DEPTH: usize = 6;
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameState {
     pub choice_path: String,
     pub current_level: usize,
     pub choices: Vec<Choice>,
+    #[serde(default)]
+    pub gems: Vec<CollectedGem>,
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    // Totals of the effects of every option picked, in the order the axes were first touched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub world: Vec<(String, i32)>,
+    // Set when a graph story ends before `max_depth`
+    #[serde(default)]
+    pub finished: bool,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::with_max_depth(DEFAULT_MAX_DEPTH)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectedGem {
+    pub path: String,
+    pub gem: TimeGem,
 }
 
 impl GameState {
@@ -12,21 +42,89 @@ impl GameState {
         Self::default()
     }
     
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            choice_path: String::new(),
+            current_level: 0,
+            choices: Vec::new(),
+            gems: Vec::new(),
+            max_depth,
+            world: Vec::new(),
+            finished: false,
+        }
+    }
+    
+    pub fn from_path(path: &str, max_depth: usize) -> Option<Self> {
+        let mut state = Self::with_max_depth(max_depth);
+        for c in path.chars() {
+            if !state.can_make_choice() {
+                return None;
+            }
+            state.add_choice(ChoiceType::from_char(c)?);
+        }
+        Some(state)
+    }
+    
     pub fn add_choice(&mut self, choice_type: ChoiceType) {
-        let choice = Choice::new(choice_type.clone(), self.current_level);
+        let choice = Choice::new(choice_type, self.current_level);
         self.choices.push(choice);
         self.choice_path.push(choice_type.as_char());
         self.current_level += 1;
     }
     
+    // Graph stories can end before the full depth; the run is complete where it stops
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+    
     pub fn reset(&mut self) {
         self.choice_path.clear();
         self.current_level = 0;
         self.choices.clear();
+        self.gems.clear();
+        self.world.clear();
+        self.finished = false;
+    }
+    
+    pub fn apply_effects(&mut self, effects: &BTreeMap<String, i32>) {
+        for (axis, delta) in effects {
+            match self.world.iter_mut().find(|(name, _)| name == axis) {
+                Some((_, value)) => *value += delta,
+                None => self.world.push((axis.clone(), *delta)),
+            }
+        }
+    }
+    
+    pub fn axis_value(&self, axis: &str) -> i32 {
+        self.world.iter().find(|(name, _)| name == axis).map_or(0, |(_, value)| *value)
+    }
+    
+    pub fn collect_gem(&mut self, gem: TimeGem) -> bool {
+        if self.choice_path.is_empty() || self.has_gem(&self.choice_path) {
+            return false;
+        }
+        self.gems.push(CollectedGem {
+            path: self.choice_path.clone(),
+            gem,
+        });
+        true
+    }
+    
+    pub fn has_gem(&self, path: &str) -> bool {
+        self.gems.iter().any(|collected| collected.path == path)
+    }
+    
+    // Keep only the gems picked up in rooms along the current path
+    pub fn restore_gems(&mut self, gems: &[CollectedGem]) {
+        self.gems = gems
+            .iter()
+            .filter(|collected| self.choice_path.starts_with(&collected.path))
+            .cloned()
+            .collect();
     }
     
     pub fn is_complete(&self) -> bool {
-        self.current_level >= 6
+        self.finished || self.current_level >= self.max_depth
     }
     
     pub fn get_path(&self) -> &str {
@@ -37,7 +135,16 @@ impl GameState {
         self.current_level
     }
     
+    // How many levels this run spans: the story's depth, or where it ended early
+    pub fn get_max_depth(&self) -> usize {
+        if self.finished {
+            self.current_level
+        } else {
+            self.max_depth
+        }
+    }
+    
     pub fn can_make_choice(&self) -> bool {
-        self.current_level < 6
+        !self.is_complete()
     }
 }
\ No newline at end of file
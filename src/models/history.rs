@@ -0,0 +1,27 @@
+use crate::models::{Locale, StoryContent, TimeGem};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub path: String,
+    pub year: Option<i32>,
+    pub title: String,
+    pub story: String,
+    pub gem: Option<TimeGem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FutureHistory {
+    pub path: String,
+    pub prologue: StoryContent,
+    pub entries: Vec<HistoryEntry>,
+    pub epilogue: StoryContent,
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+impl FutureHistory {
+    pub fn gem_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.gem.is_some()).count()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::models::{ChoiceType, CollectedGem, GameState};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletedRun {
+    pub path: String,
+    pub gems: Vec<CollectedGem>,
+    pub completed_at: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoopMemory {
+    pub runs: Vec<CompletedRun>,
+}
+
+impl LoopMemory {
+    pub fn record_run(&mut self, game_state: &GameState, completed_at: f64) -> bool {
+        if !game_state.is_complete() {
+            return false;
+        }
+        self.runs.push(CompletedRun {
+            path: game_state.get_path().to_string(),
+            gems: game_state.gems.clone(),
+            completed_at,
+        });
+        true
+    }
+    
+    pub fn loop_count(&self) -> usize {
+        self.runs.len()
+    }
+    
+    pub fn is_explored(&self, path: &str) -> bool {
+        self.runs.iter().any(|run| run.path.starts_with(path))
+    }
+    
+    pub fn explored_choices(&self, path: &str) -> Vec<ChoiceType> {
+        let mut explored = Vec::new();
+        for run in &self.runs {
+            let next = run
+                .path
+                .strip_prefix(path)
+                .and_then(|rest| rest.chars().next())
+                .and_then(ChoiceType::from_char);
+            if let Some(choice) = next {
+                if !explored.contains(&choice) {
+                    explored.push(choice);
+                }
+            }
+        }
+        explored
+    }
+    
+    // Every distinct gem ever picked up, in the order it was first found
+    pub fn remembered_gems(&self) -> Vec<CollectedGem> {
+        let mut gems: Vec<CollectedGem> = Vec::new();
+        for collected in self.runs.iter().flat_map(|run| run.gems.iter()) {
+            if !gems.iter().any(|known| known.path == collected.path) {
+                gems.push(collected.clone());
+            }
+        }
+        gems
+    }
+}
@@ -1,7 +1,29 @@
 pub mod story;
+pub mod story_graph;
 pub mod choice;
 pub mod game_state;
+pub mod save_slot;
+pub mod history;
+pub mod loop_memory;
+pub mod locale;
+pub mod translation;
+pub mod text_speed;
+pub mod scenario;
+pub mod world;
+pub mod voting;
+pub mod choice_stats;
 
 pub use story::*;
+pub use story_graph::*;
 pub use choice::*;
-pub use game_state::*;
\ No newline at end of file
+pub use game_state::*;
+pub use save_slot::*;
+pub use history::*;
+pub use loop_memory::*;
+pub use locale::*;
+pub use translation::*;
+pub use text_speed::*;
+pub use scenario::*;
+pub use world::*;
+pub use voting::*;
+pub use choice_stats::*;
\ No newline at end of file
@@ -0,0 +1,69 @@
+use crate::models::{GameState, Locale};
+use crate::utils::I18n;
+use serde::{Deserialize, Serialize};
+
+// Bump only when older saves can no longer be read as they are. Fields added to
+// `GameState` since v1 (gems, max_depth, world) default when missing, so v1 saves still load
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSlot {
+    pub version: u32,
+    pub name: String,
+    pub saved_at: f64,
+    pub state: GameState,
+    // Set for a stored slot this build couldn't parse
+    #[serde(skip)]
+    unreadable: bool,
+}
+
+impl SaveSlot {
+    pub fn new(name: impl Into<String>, saved_at: f64, state: GameState) -> Self {
+        Self {
+            version: SAVE_FORMAT_VERSION,
+            name: name.into(),
+            saved_at,
+            state,
+            unreadable: false,
+        }
+    }
+
+    // Slots are parsed one at a time, so one this build can't read still lists under its name
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        serde_json::from_value(value.clone()).unwrap_or_else(|_| Self {
+            version: value["version"].as_u64().unwrap_or_default() as u32,
+            name: Self::stored_name(value).unwrap_or_default().to_string(),
+            saved_at: value["saved_at"].as_f64().unwrap_or_default(),
+            state: GameState::new(),
+            unreadable: true,
+        })
+    }
+
+    // The stored slot list as raw values; a missing list is empty, but one that
+    // isn't a JSON array is an error so nobody saves over it
+    pub fn parse_stored(raw: Option<&str>) -> serde_json::Result<Vec<serde_json::Value>> {
+        raw.map_or(Ok(Vec::new()), serde_json::from_str)
+    }
+
+    pub fn stored_name(value: &serde_json::Value) -> Option<&str> {
+        value["name"].as_str()
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        !self.unreadable && self.version == SAVE_FORMAT_VERSION
+    }
+
+    pub fn path_preview(&self, locale: Locale) -> String {
+        let path = self.state.get_path();
+        if !self.is_compatible() {
+            I18n::t(locale, "control.incompatible").to_string()
+        } else if path.is_empty() {
+            I18n::t(locale, "app.gate").to_string()
+        } else {
+            path.chars()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(" → ")
+        }
+    }
+}
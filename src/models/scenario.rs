@@ -0,0 +1,88 @@
+use crate::models::Locale;
+use crate::utils::I18n;
+use serde::{Deserialize, Serialize};
+
+// The twelve AI aftermath scenarios from Life 3.0, chapter 5; every ending is
+// tagged with the one it comes closest to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scenario {
+    LibertarianUtopia,
+    BenevolentDictator,
+    EgalitarianUtopia,
+    Gatekeeper,
+    ProtectorGod,
+    EnslavedGod,
+    Conquerors,
+    Descendants,
+    Zookeeper,
+    #[serde(rename = "1984")]
+    NineteenEightyFour,
+    Reversion,
+    SelfDestruction,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 12] = [
+        Scenario::LibertarianUtopia,
+        Scenario::BenevolentDictator,
+        Scenario::EgalitarianUtopia,
+        Scenario::Gatekeeper,
+        Scenario::ProtectorGod,
+        Scenario::EnslavedGod,
+        Scenario::Conquerors,
+        Scenario::Descendants,
+        Scenario::Zookeeper,
+        Scenario::NineteenEightyFour,
+        Scenario::Reversion,
+        Scenario::SelfDestruction,
+    ];
+
+    // The value written in story files, e.g. `scenario = "protector-god"`
+    pub fn key(self) -> &'static str {
+        match self {
+            Scenario::LibertarianUtopia => "libertarian-utopia",
+            Scenario::BenevolentDictator => "benevolent-dictator",
+            Scenario::EgalitarianUtopia => "egalitarian-utopia",
+            Scenario::Gatekeeper => "gatekeeper",
+            Scenario::ProtectorGod => "protector-god",
+            Scenario::EnslavedGod => "enslaved-god",
+            Scenario::Conquerors => "conquerors",
+            Scenario::Descendants => "descendants",
+            Scenario::Zookeeper => "zookeeper",
+            Scenario::NineteenEightyFour => "1984",
+            Scenario::Reversion => "reversion",
+            Scenario::SelfDestruction => "self-destruction",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scenario| scenario.key() == key)
+    }
+
+    pub fn name(self, locale: Locale) -> &'static str {
+        I18n::t(locale, self.catalog_keys().0)
+    }
+
+    // What the scenario means in the book, in a sentence or two
+    pub fn description(self, locale: Locale) -> &'static str {
+        I18n::t(locale, self.catalog_keys().1)
+    }
+
+    fn catalog_keys(self) -> (&'static str, &'static str) {
+        match self {
+            Scenario::LibertarianUtopia => ("scenario.libertarian-utopia.name", "scenario.libertarian-utopia.about"),
+            Scenario::BenevolentDictator => ("scenario.benevolent-dictator.name", "scenario.benevolent-dictator.about"),
+            Scenario::EgalitarianUtopia => ("scenario.egalitarian-utopia.name", "scenario.egalitarian-utopia.about"),
+            Scenario::Gatekeeper => ("scenario.gatekeeper.name", "scenario.gatekeeper.about"),
+            Scenario::ProtectorGod => ("scenario.protector-god.name", "scenario.protector-god.about"),
+            Scenario::EnslavedGod => ("scenario.enslaved-god.name", "scenario.enslaved-god.about"),
+            Scenario::Conquerors => ("scenario.conquerors.name", "scenario.conquerors.about"),
+            Scenario::Descendants => ("scenario.descendants.name", "scenario.descendants.about"),
+            Scenario::Zookeeper => ("scenario.zookeeper.name", "scenario.zookeeper.about"),
+            Scenario::NineteenEightyFour => ("scenario.1984.name", "scenario.1984.about"),
+            Scenario::Reversion => ("scenario.reversion.name", "scenario.reversion.about"),
+            Scenario::SelfDestruction => ("scenario.self-destruction.name", "scenario.self-destruction.about"),
+        }
+    }
+}
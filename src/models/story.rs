@@ -1,30 +1,155 @@
+use crate::models::{Axis, ChoiceOption, ChoiceType, GameState, GraphNode, Scenario, DEFAULT_START_NODE};
+use crate::utils::TreeLayout;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoryData {
-    #[serde(rename = "FM_CHOICE")]
+    #[serde(rename = "FM_CHOICE", default)]
     pub fm_choice: HashMap<String, ChoiceData>,
-    #[serde(rename = "FM_STORY")]
+    #[serde(rename = "FM_STORY", default)]
     pub fm_story: HashMap<String, StoryContent>,
     #[serde(rename = "FM_START")]
     pub fm_start: StoryContent,
     #[serde(rename = "FM_NOEND")]
     pub fm_noend: StoryContent,
+    #[serde(rename = "FM_META", default)]
+    pub fm_meta: StoryMeta,
+    #[serde(rename = "FM_NODE", default, skip_serializing_if = "HashMap::is_empty")]
+    pub fm_node: HashMap<String, GraphNode>,
+    #[serde(rename = "FM_ENDING", default, skip_serializing_if = "StoryEndings::is_empty")]
+    pub fm_ending: StoryEndings,
+}
+
+// Endings for finished runs, by full path or by the scenario of the last room;
+// FM_NOEND covers every other ending
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StoryEndings {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub paths: HashMap<String, StoryContent>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scenarios: HashMap<String, StoryContent>,
+}
+
+impl StoryEndings {
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.scenarios.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoryMeta {
+    pub max_depth: Option<usize>,
+    pub start: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoryContent {
     pub title: String,
     pub story: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gem: Option<TimeGem>,
+    // Which Life 3.0 aftermath an ending stands for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<Scenario>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeGem {
+    pub name: String,
+    pub year: i32,
+    pub description: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "ChoiceDataToml")]
 pub struct ChoiceData {
     pub title: String,
     pub story: String,
-    pub red: String,
-    pub blue: String,
+    pub options: Vec<ChoiceOption>,
+}
+
+// Older story files only have `red`/`blue`; newer ones list `options` in order
+#[derive(Deserialize)]
+struct ChoiceDataToml {
+    title: String,
+    story: String,
+    red: Option<String>,
+    blue: Option<String>,
+    #[serde(default)]
+    red_effects: BTreeMap<String, i32>,
+    #[serde(default)]
+    blue_effects: BTreeMap<String, i32>,
+    #[serde(default)]
+    options: Vec<ChoiceOption>,
+}
+
+impl From<ChoiceDataToml> for ChoiceData {
+    fn from(raw: ChoiceDataToml) -> Self {
+        let mut options = raw.options;
+        if options.is_empty() {
+            let legacy = [
+                (ChoiceType::Red, "red", raw.red, raw.red_effects),
+                (ChoiceType::Blue, "blue", raw.blue, raw.blue_effects),
+            ];
+            for (choice_type, color, label, effects) in legacy {
+                if let Some(label) = label {
+                    options.push(ChoiceOption {
+                        key: choice_type.as_char(),
+                        label,
+                        color: color.to_string(),
+                        target: None,
+                        effects,
+                    });
+                }
+            }
+        }
+        Self {
+            title: raw.title,
+            story: raw.story,
+            options,
+        }
+    }
+}
+
+impl ChoiceData {
+    pub fn option(&self, choice_type: ChoiceType) -> Option<&ChoiceOption> {
+        self.options.iter().find(|option| option.key == choice_type.as_char())
+    }
+    
+    pub fn choice_types(&self) -> Vec<ChoiceType> {
+        self.options.iter().filter_map(|option| option.choice_type()).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryNode {
+    pub path: String,
+    pub node_id: String,
+    pub max_depth: usize,
+    pub ending_count: usize,
+    pub intro: Option<ChoiceData>,
+    pub story: Option<StoryContent>,
+    pub choice: Option<ChoiceData>,
+    pub final_story: Option<StoryContent>,
+    pub tree: String,
+    pub layout: TreeLayout,
+    pub axes: Vec<Axis>,
+    // Where the run so far has moved each axis, in the order they were first touched
+    pub world: Vec<(String, i32)>,
+}
+
+impl StoryNode {
+    // The run the node stands for, rebuilt from its path on the client
+    pub fn game_state(&self) -> Option<GameState> {
+        let mut state = GameState::from_path(&self.path, self.max_depth)?;
+        state.world = self.world.clone();
+        if self.final_story.is_some() {
+            state.finish();
+        }
+        Some(state)
+    }
 }
 
 impl StoryData {
@@ -40,7 +165,40 @@ impl StoryData {
         self.fm_choice.get(&level.to_string())
     }
     
+    // FM_CHOICE.0 frames the whole game; FM_CHOICE.n leads into level n
+    pub fn get_intro(&self) -> Option<&ChoiceData> {
+        self.get_choice_by_level(0)
+    }
+    
+    // Depth of the path-keyed layout: FM_META.max_depth or its longest FM_STORY path
+    pub fn path_depth(&self) -> usize {
+        self.fm_meta.max_depth.unwrap_or_else(|| {
+            self.fm_story.keys().map(|path| path.chars().count()).max().unwrap_or(0)
+        })
+    }
+    
+    // The TOML table a graph node was read from
+    pub fn section_key(&self, id: &str) -> String {
+        if self.is_graph() {
+            format!("FM_NODE.{}", id)
+        } else if id.is_empty() {
+            "FM_START".to_string()
+        } else {
+            format!("FM_STORY.{}", id)
+        }
+    }
+
+    pub fn is_graph(&self) -> bool {
+        !self.fm_node.is_empty()
+    }
+    
+    pub fn start_node(&self) -> &str {
+        self.fm_meta.start.as_deref().unwrap_or(DEFAULT_START_NODE)
+    }
+    
+    // The ending shown when no FM_ENDING entry matches the run
     pub fn get_final_story(&self) -> &StoryContent {
         &self.fm_noend
     }
+
 }
\ No newline at end of file
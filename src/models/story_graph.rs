@@ -0,0 +1,322 @@
+use crate::models::{Axis, ChoiceData, ChoiceType, StoryContent, StoryData, StoryEndings};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub const DEFAULT_START_NODE: &str = "start";
+
+// A room in a graph story; its options name the rooms they lead to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    #[serde(flatten)]
+    pub content: StoryContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choice: Option<ChoiceData>,
+}
+
+impl GraphNode {
+    pub fn is_ending(&self) -> bool {
+        self.choice.as_ref().is_none_or(|choice| choice.options.is_empty())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StoryGraph {
+    pub start: String,
+    pub nodes: HashMap<String, GraphNode>,
+    pub intro: Option<ChoiceData>,
+    pub prologue: StoryContent,
+    pub ending: StoryContent,
+    pub endings: StoryEndings,
+    pub max_depth: usize,
+    // Both walk the whole graph, so they are measured once when it is built
+    ending_count: usize,
+    axes: Vec<Axis>,
+}
+
+impl StoryGraph {
+    pub fn from_story_data(story_data: &StoryData) -> Self {
+        if story_data.fm_node.is_empty() {
+            return Self::from_paths(story_data);
+        }
+
+        let mut graph = Self {
+            start: story_data.start_node().to_string(),
+            nodes: story_data.fm_node.clone(),
+            intro: story_data.get_intro().cloned(),
+            prologue: story_data.fm_start.clone(),
+            ending: story_data.get_final_story().clone(),
+            endings: story_data.fm_ending.clone(),
+            max_depth: 0,
+            ending_count: 0,
+            axes: Vec::new(),
+        };
+        graph.max_depth = story_data
+            .fm_meta
+            .max_depth
+            .unwrap_or_else(|| graph.longest_path());
+        graph.measured()
+    }
+
+    // Path-keyed stories are trees: every path is its own room, and
+    // FM_CHOICE.n leads from each level n-1 room to its children
+    fn from_paths(story_data: &StoryData) -> Self {
+        let max_depth = story_data.path_depth();
+        let mut nodes = HashMap::new();
+        let rooms = std::iter::once((String::new(), &story_data.fm_start))
+            .chain(story_data.fm_story.iter().map(|(path, content)| (path.clone(), content)));
+
+        for (path, content) in rooms {
+            let level = path.chars().count();
+            let choice = if level < max_depth {
+                story_data.get_choice_by_level(level + 1).cloned().map(|mut choice| {
+                    for option in &mut choice.options {
+                        option.target = Some(format!("{}{}", path, option.key));
+                    }
+                    choice
+                })
+            } else {
+                None
+            };
+            nodes.insert(path, GraphNode {
+                content: content.clone(),
+                choice,
+            });
+        }
+
+        Self {
+            start: String::new(),
+            nodes,
+            intro: story_data.get_intro().cloned(),
+            prologue: story_data.fm_start.clone(),
+            ending: story_data.get_final_story().clone(),
+            endings: story_data.fm_ending.clone(),
+            max_depth,
+            ending_count: 0,
+            axes: Vec::new(),
+        }
+        .measured()
+    }
+
+    fn measured(mut self) -> Self {
+        self.ending_count = self.count_endings();
+        self.axes = self.collect_axes();
+        self
+    }
+
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.get(id)
+    }
+
+    pub fn follow(&self, id: &str, choice_type: ChoiceType) -> Option<&str> {
+        let target = self.node(id)?.choice.as_ref()?.option(choice_type)?.target.as_deref()?;
+        self.nodes.contains_key(target).then_some(target)
+    }
+
+    // Node IDs visited along a path, starting with the start node
+    pub fn walk(&self, path: &str) -> Option<Vec<&str>> {
+        let mut current = self.nodes.get_key_value(&self.start)?.0.as_str();
+        let mut visited = vec![current];
+        for c in path.chars() {
+            current = self.follow(current, ChoiceType::from_char(c)?)?;
+            visited.push(current);
+        }
+        Some(visited)
+    }
+
+    pub fn story_at(&self, path: &str) -> Option<&StoryContent> {
+        let id = *self.walk(path)?.last()?;
+        self.node(id).map(|node| &node.content)
+    }
+
+    // The FM_ENDING entry a finished run gets: its own path first, then the
+    // scenario of the room it ends in
+    pub fn ending_key(&self, path: &str) -> Option<String> {
+        if self.endings.paths.contains_key(path) {
+            return Some(format!("paths.{}", path));
+        }
+        let scenario = self.story_at(path)?.scenario?;
+        self.endings
+            .scenarios
+            .contains_key(scenario.key())
+            .then(|| format!("scenarios.{}", scenario.key()))
+    }
+
+    pub fn ending_at(&self, path: &str) -> &StoryContent {
+        self.endings
+            .paths
+            .get(path)
+            .or_else(|| {
+                let scenario = self.story_at(path)?.scenario?;
+                self.endings.scenarios.get(scenario.key())
+            })
+            .unwrap_or(&self.ending)
+    }
+
+    // Fewest choices needed to reach each node; unreachable nodes are left out
+    pub fn depths(&self) -> HashMap<&str, usize> {
+        let mut depths = HashMap::new();
+        let Some((start, _)) = self.nodes.get_key_value(&self.start) else {
+            return depths;
+        };
+        depths.insert(start.as_str(), 0);
+        let mut queue = VecDeque::from([start.as_str()]);
+        while let Some(id) = queue.pop_front() {
+            let depth = depths[id];
+            let targets = self
+                .node(id)
+                .and_then(|node| node.choice.as_ref())
+                .into_iter()
+                .flat_map(|choice| choice.options.iter())
+                .filter_map(|option| option.target.as_deref());
+            for target in targets {
+                if let Some((target, _)) = self.nodes.get_key_value(target) {
+                    if !depths.contains_key(target.as_str()) {
+                        depths.insert(target.as_str(), depth + 1);
+                        queue.push_back(target.as_str());
+                    }
+                }
+            }
+        }
+        depths
+    }
+
+    // Distinct runs from the start; a run also ends when it runs out of depth
+    pub fn ending_count(&self) -> usize {
+        self.ending_count
+    }
+
+    fn count_endings(&self) -> usize {
+        let mut memo = HashMap::new();
+        self.count_runs(&self.start, self.max_depth, &mut memo)
+    }
+
+    fn count_runs<'a>(&'a self, id: &'a str, remaining: usize, memo: &mut HashMap<(&'a str, usize), usize>) -> usize {
+        let Some(node) = self.node(id) else {
+            return 0;
+        };
+        let Some(choice) = node.choice.as_ref().filter(|_| remaining > 0 && !node.is_ending()) else {
+            return 1;
+        };
+        if let Some(&count) = memo.get(&(id, remaining)) {
+            return count;
+        }
+        let count = choice
+            .options
+            .iter()
+            .filter_map(|option| option.target.as_deref())
+            .map(|target| self.count_runs(target, remaining - 1, memo))
+            .fold(0usize, usize::saturating_add);
+        memo.insert((id, remaining), count);
+        count
+    }
+
+    // Every axis the options move, nearest rooms first, with the range of
+    // totals a run can end up with
+    pub fn axes(&self) -> &[Axis] {
+        &self.axes
+    }
+
+    fn collect_axes(&self) -> Vec<Axis> {
+        let mut rooms: Vec<(&str, usize)> = self.depths().into_iter().collect();
+        rooms.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+        let mut names: Vec<&str> = Vec::new();
+        for (id, _) in rooms {
+            let effects = self
+                .node(id)
+                .and_then(|node| node.choice.as_ref())
+                .into_iter()
+                .flat_map(|choice| choice.options.iter())
+                .flat_map(|option| option.effects.keys());
+            for name in effects {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .map(|name| {
+                let mut memo = HashMap::new();
+                let (min, max) = self.axis_range(name, &self.start, self.max_depth, &mut memo);
+                Axis { name: name.to_string(), min, max }
+            })
+            .collect()
+    }
+
+    fn axis_range<'a>(&'a self, axis: &str, id: &'a str, remaining: usize, memo: &mut HashMap<(&'a str, usize), (i32, i32)>) -> (i32, i32) {
+        let Some(choice) = self
+            .node(id)
+            .filter(|node| remaining > 0 && !node.is_ending())
+            .and_then(|node| node.choice.as_ref())
+        else {
+            return (0, 0);
+        };
+        if let Some(&range) = memo.get(&(id, remaining)) {
+            return range;
+        }
+        let mut range: Option<(i32, i32)> = None;
+        for option in &choice.options {
+            let Some(target) = option.target.as_deref().filter(|target| self.nodes.contains_key(*target)) else {
+                continue;
+            };
+            let delta = option.effects.get(axis).copied().unwrap_or(0);
+            let (low, high) = self.axis_range(axis, target, remaining - 1, memo);
+            range = Some(match range {
+                Some((min, max)) => (min.min(low + delta), max.max(high + delta)),
+                None => (low + delta, high + delta),
+            });
+        }
+        let range = range.unwrap_or((0, 0));
+        memo.insert((id, remaining), range);
+        range
+    }
+
+    fn longest_path(&self) -> usize {
+        // A room's depth depends on the rooms already on the path once the story
+        // loops, so only a loop-free story can reuse it between branches
+        let mut memo = (!self.has_loop(&self.start, &mut HashSet::new(), &mut HashSet::new())).then(HashMap::new);
+        self.longest_from(&self.start, &mut HashSet::new(), &mut memo)
+    }
+
+    fn has_loop<'a>(&'a self, id: &'a str, on_path: &mut HashSet<&'a str>, done: &mut HashSet<&'a str>) -> bool {
+        if done.contains(id) {
+            return false;
+        }
+        let Some(choice) = self.node(id).and_then(|node| node.choice.as_ref()) else {
+            return false;
+        };
+        on_path.insert(id);
+        for target in choice.options.iter().filter_map(|option| option.target.as_deref()) {
+            if self.nodes.contains_key(target) && (on_path.contains(target) || self.has_loop(target, on_path, done)) {
+                return true;
+            }
+        }
+        on_path.remove(id);
+        done.insert(id);
+        false
+    }
+
+    // Loops don't add depth, so stories that loop should declare FM_META.max_depth;
+    // without loops merged rooms are measured once, or wide merging stories would take forever
+    fn longest_from<'a>(&'a self, id: &'a str, on_path: &mut HashSet<&'a str>, memo: &mut Option<HashMap<&'a str, usize>>) -> usize {
+        let Some(choice) = self.node(id).and_then(|node| node.choice.as_ref()) else {
+            return 0;
+        };
+        if let Some(&longest) = memo.as_ref().and_then(|memo| memo.get(id)) {
+            return longest;
+        }
+        on_path.insert(id);
+        let mut longest = 0;
+        for target in choice.options.iter().filter_map(|option| option.target.as_deref()) {
+            if self.nodes.contains_key(target) && !on_path.contains(target) {
+                longest = longest.max(1 + self.longest_from(target, on_path, memo));
+            }
+        }
+        on_path.remove(id);
+        if let Some(memo) = memo {
+            memo.insert(id, longest);
+        }
+        longest
+    }
+}
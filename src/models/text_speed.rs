@@ -0,0 +1,51 @@
+use crate::models::Locale;
+use crate::utils::I18n;
+use serde::{Deserialize, Serialize};
+
+// How fast story text streams in; the choice is remembered per browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+    Instant,
+}
+
+impl TextSpeed {
+    pub const ALL: [TextSpeed; 4] = [TextSpeed::Slow, TextSpeed::Normal, TextSpeed::Fast, TextSpeed::Instant];
+
+    // Per character, so a long clause waits longer before the next one appears
+    pub fn delay_ms(self) -> u64 {
+        match self {
+            TextSpeed::Slow => 80,
+            TextSpeed::Normal => 50,
+            TextSpeed::Fast => 20,
+            TextSpeed::Instant => 0,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            TextSpeed::Slow => "slow",
+            TextSpeed::Normal => "normal",
+            TextSpeed::Fast => "fast",
+            TextSpeed::Instant => "instant",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|speed| speed.key() == key)
+    }
+
+    pub fn name(self, locale: Locale) -> &'static str {
+        let key = match self {
+            TextSpeed::Slow => "control.speed_slow",
+            TextSpeed::Normal => "control.speed_normal",
+            TextSpeed::Fast => "control.speed_fast",
+            TextSpeed::Instant => "control.speed_instant",
+        };
+        I18n::t(locale, key)
+    }
+}
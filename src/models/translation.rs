@@ -0,0 +1,144 @@
+use crate::models::{ChoiceData, ChoiceOption, GraphNode, StoryContent, StoryData, StoryEndings, TimeGem};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// A story pack for another locale; anything it leaves out falls back to the default pack
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StoryTranslation {
+    #[serde(rename = "FM_CHOICE", default)]
+    pub fm_choice: HashMap<String, ChoiceData>,
+    #[serde(rename = "FM_STORY", default)]
+    pub fm_story: HashMap<String, StoryContent>,
+    #[serde(rename = "FM_NODE", default)]
+    pub fm_node: HashMap<String, GraphNode>,
+    #[serde(rename = "FM_START")]
+    pub fm_start: Option<StoryContent>,
+    #[serde(rename = "FM_NOEND")]
+    pub fm_noend: Option<StoryContent>,
+    #[serde(rename = "FM_ENDING", default)]
+    pub fm_ending: StoryEndings,
+}
+
+impl StoryTranslation {
+    // Sections of `story_data` this pack has no text for; they show in the default locale
+    pub fn untranslated(&self, story_data: &StoryData) -> Vec<String> {
+        let mut missing = Vec::new();
+        if self.fm_start.is_none() {
+            missing.push("FM_START".to_string());
+        }
+        if self.fm_noend.is_none() {
+            missing.push("FM_NOEND".to_string());
+        }
+        missing.extend(missing_keys("FM_CHOICE", &story_data.fm_choice, &self.fm_choice));
+        missing.extend(missing_keys("FM_STORY", &story_data.fm_story, &self.fm_story));
+        missing.extend(missing_keys("FM_NODE", &story_data.fm_node, &self.fm_node));
+        missing.extend(missing_keys("FM_ENDING.paths", &story_data.fm_ending.paths, &self.fm_ending.paths));
+        missing.extend(missing_keys("FM_ENDING.scenarios", &story_data.fm_ending.scenarios, &self.fm_ending.scenarios));
+        missing.sort();
+        missing
+    }
+}
+
+fn missing_keys<T, U>(section: &str, authored: &HashMap<String, T>, translated: &HashMap<String, U>) -> Vec<String> {
+    authored
+        .keys()
+        .filter(|key| !translated.contains_key(*key))
+        .map(|key| format!("{}.{}", section, key))
+        .collect()
+}
+
+impl StoryData {
+    // Only text is translated: paths, option keys, targets and gem years stay as authored
+    pub fn localized(&self, translation: &StoryTranslation) -> StoryData {
+        StoryData {
+            fm_choice: self
+                .fm_choice
+                .iter()
+                .map(|(key, choice)| (key.clone(), choice.localized(translation.fm_choice.get(key))))
+                .collect(),
+            fm_story: self
+                .fm_story
+                .iter()
+                .map(|(path, content)| (path.clone(), content.localized(translation.fm_story.get(path))))
+                .collect(),
+            fm_start: self.fm_start.localized(translation.fm_start.as_ref()),
+            fm_noend: self.fm_noend.localized(translation.fm_noend.as_ref()),
+            fm_meta: self.fm_meta.clone(),
+            fm_node: self
+                .fm_node
+                .iter()
+                .map(|(id, node)| {
+                    let translated = translation.fm_node.get(id);
+                    (id.clone(), GraphNode {
+                        content: node.content.localized(translated.map(|t| &t.content)),
+                        choice: node
+                            .choice
+                            .as_ref()
+                            .map(|choice| choice.localized(translated.and_then(|t| t.choice.as_ref()))),
+                    })
+                })
+                .collect(),
+            fm_ending: self.fm_ending.localized(&translation.fm_ending),
+        }
+    }
+}
+
+impl StoryEndings {
+    pub fn localized(&self, translated: &StoryEndings) -> StoryEndings {
+        let localize = |entries: &HashMap<String, StoryContent>, translated: &HashMap<String, StoryContent>| {
+            entries
+                .iter()
+                .map(|(key, content)| (key.clone(), content.localized(translated.get(key))))
+                .collect()
+        };
+        StoryEndings {
+            paths: localize(&self.paths, &translated.paths),
+            scenarios: localize(&self.scenarios, &translated.scenarios),
+        }
+    }
+}
+
+impl StoryContent {
+    pub fn localized(&self, translated: Option<&StoryContent>) -> StoryContent {
+        let Some(translated) = translated else {
+            return self.clone();
+        };
+        StoryContent {
+            title: translated.title.clone(),
+            story: translated.story.clone(),
+            gem: self.gem.as_ref().map(|gem| match &translated.gem {
+                Some(t) => TimeGem {
+                    name: t.name.clone(),
+                    year: gem.year,
+                    description: t.description.clone(),
+                },
+                None => gem.clone(),
+            }),
+            scenario: self.scenario,
+        }
+    }
+}
+
+impl ChoiceData {
+    pub fn localized(&self, translated: Option<&ChoiceData>) -> ChoiceData {
+        let Some(translated) = translated else {
+            return self.clone();
+        };
+        ChoiceData {
+            title: translated.title.clone(),
+            story: translated.story.clone(),
+            options: self
+                .options
+                .iter()
+                .map(|option| ChoiceOption {
+                    label: translated
+                        .options
+                        .iter()
+                        .find(|t| t.key == option.key)
+                        .map_or_else(|| option.label.clone(), |t| t.label.clone()),
+                    ..option.clone()
+                })
+                .collect(),
+        }
+    }
+}
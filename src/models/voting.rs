@@ -0,0 +1,122 @@
+use crate::models::ChoiceData;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+pub const DEFAULT_VOTE_SECONDS: u32 = 30;
+pub const MAX_VOTE_SECONDS: u32 = 300;
+pub const JOIN_CODE_LEN: usize = 4;
+// No 0/O or 1/I, so a code reads the same off a projector from the back row
+const JOIN_CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+pub fn join_code() -> String {
+    // Every RandomState is seeded afresh, which is random enough for a room code
+    let mut seed = RandomState::new().build_hasher().finish();
+    (0..JOIN_CODE_LEN)
+        .map(|_| {
+            let c = JOIN_CODE_CHARS[(seed % JOIN_CODE_CHARS.len() as u64) as usize];
+            seed /= JOIN_CODE_CHARS.len() as u64;
+            c as char
+        })
+        .collect()
+}
+
+// What people type is forgiving about case and spaces
+pub fn normalize_join_code(input: &str) -> Option<String> {
+    let code: String = input.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_uppercase();
+    let valid = code.len() == JOIN_CODE_LEN && code.bytes().all(|c| JOIN_CODE_CHARS.contains(&c));
+    valid.then_some(code)
+}
+
+// Sent over the voting socket: the presenter opens and closes votes, the audience votes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VoteRequest {
+    Open { path: String, seconds: u32 },
+    Close,
+    Vote { key: char },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "phase", rename_all = "snake_case")]
+pub enum VotePhase {
+    Waiting,
+    Open { seconds_left: u32 },
+    // `None` when nobody voted, so the game stays where it was
+    Decided { key: Option<char> },
+    Ended,
+}
+
+// Everything a screen in the room needs to draw itself, sent after every change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRoomView {
+    pub code: String,
+    pub path: String,
+    pub choice: Option<ChoiceData>,
+    pub phase: VotePhase,
+    pub tallies: Vec<(char, usize)>,
+    pub voters: usize,
+}
+
+impl VoteRoomView {
+    pub fn total_votes(&self) -> usize {
+        self.tallies.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.phase, VotePhase::Open { .. })
+    }
+}
+
+// One vote on one choice: everyone has a single vote and may change it until time is up
+#[derive(Debug, Clone)]
+pub struct VoteRound {
+    options: Vec<char>,
+    votes: HashMap<u64, char>,
+    pub seconds_left: u32,
+}
+
+impl VoteRound {
+    pub fn new(choice: &ChoiceData, seconds: u32) -> Self {
+        Self {
+            options: choice.options.iter().map(|option| option.key).collect(),
+            votes: HashMap::new(),
+            seconds_left: seconds.clamp(1, MAX_VOTE_SECONDS),
+        }
+    }
+
+    pub fn cast(&mut self, voter: u64, key: char) -> bool {
+        if self.seconds_left == 0 || !self.options.contains(&key) {
+            return false;
+        }
+        self.votes.insert(voter, key);
+        true
+    }
+
+    // In the order the choice lists its options
+    pub fn tallies(&self) -> Vec<(char, usize)> {
+        self.options
+            .iter()
+            .map(|key| (*key, self.votes.values().filter(|vote| *vote == key).count()))
+            .collect()
+    }
+
+    // A tie goes to the option listed first
+    pub fn majority(&self) -> Option<char> {
+        self.tallies()
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .fold(None, |best: Option<(char, usize)>, (key, count)| match best {
+                Some((_, most)) if most >= count => best,
+                _ => Some((key, count)),
+            })
+            .map(|(key, _)| key)
+    }
+
+    // One second passes; `false` once time is up
+    pub fn tick(&mut self) -> bool {
+        self.seconds_left = self.seconds_left.saturating_sub(1);
+        self.seconds_left > 0
+    }
+}
@@ -0,0 +1,31 @@
+use crate::models::Locale;
+use crate::utils::I18n;
+use serde::{Deserialize, Serialize};
+
+// A world-state axis that choices push around, e.g. `safety`, with the lowest
+// and highest total any run through the story can reach
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Axis {
+    pub name: String,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Axis {
+    // Where `value` sits between the extremes, 0.0 to 1.0; flat axes sit halfway
+    pub fn fraction(&self, value: i32) -> f64 {
+        if self.max <= self.min {
+            return 0.5;
+        }
+        (value.clamp(self.min, self.max) - self.min) as f64 / (self.max - self.min) as f64
+    }
+
+    pub fn label(&self, locale: Locale) -> String {
+        Self::name_label(&self.name, locale)
+    }
+
+    // The six questions of the default story have catalog names; other axes show as written
+    pub fn name_label(name: &str, locale: Locale) -> String {
+        I18n::lookup(locale, &format!("axis.{}", name)).map_or_else(|| name.to_string(), str::to_string)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::models::ChoiceStats;
+use crate::services::PathNavigator;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ChoiceLogError {
+    #[error("Choice log database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Path `{path}` doesn't offer option `{key}`")]
+    NotOffered { path: String, key: char },
+}
+
+// Every decision players make, kept only as a running count per node and option;
+// nothing ties a count back to who chose
+#[derive(Clone)]
+pub struct ChoiceLog {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl ChoiceLog {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ChoiceLogError> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self, ChoiceLogError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(db: Connection) -> Result<Self, ChoiceLogError> {
+        db.execute_batch(
+            "CREATE TABLE IF NOT EXISTS choice_counts (
+                path TEXT NOT NULL,
+                choice TEXT NOT NULL,
+                count INTEGER NOT NULL,
+                PRIMARY KEY (path, choice)
+            )",
+        )?;
+        Ok(Self {
+            db: Arc::new(Mutex::new(db)),
+        })
+    }
+
+    fn db(&self) -> MutexGuard<'_, Connection> {
+        self.db.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Counts choosing `key` at the choice `path` leads to; only options the story offers count
+    pub fn record(&self, navigator: &PathNavigator, path: &str, key: char) -> Result<(), ChoiceLogError> {
+        let offered = navigator
+            .state_for_path(path)
+            .and_then(|state| navigator.get_current_choice(&state).cloned())
+            .is_some_and(|choice| choice.options.iter().any(|option| option.key == key));
+        if !offered {
+            return Err(ChoiceLogError::NotOffered { path: path.to_string(), key });
+        }
+        self.db().execute(
+            "INSERT INTO choice_counts (path, choice, count) VALUES (?1, ?2, 1)
+             ON CONFLICT (path, choice) DO UPDATE SET count = count + 1",
+            params![path, key.to_string()],
+        )?;
+        Ok(())
+    }
+
+    pub fn counts(&self, path: &str) -> Result<HashMap<char, u64>, ChoiceLogError> {
+        let db = self.db();
+        let mut query = db.prepare("SELECT choice, count FROM choice_counts WHERE path = ?1")?;
+        let rows = query.query_map(params![path], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        let mut counts = HashMap::new();
+        for row in rows {
+            let (choice, count) = row?;
+            if let Some(key) = choice.chars().next() {
+                counts.insert(key, count.max(0) as u64);
+            }
+        }
+        Ok(counts)
+    }
+
+    // `None` where the path doesn't lead to a choice
+    pub fn stats(&self, navigator: &PathNavigator, path: &str) -> Result<Option<ChoiceStats>, ChoiceLogError> {
+        let Some(choice) = navigator
+            .state_for_path(path)
+            .and_then(|state| navigator.get_current_choice(&state).cloned())
+        else {
+            return Ok(None);
+        };
+        Ok(Some(ChoiceStats::new(path, &choice, &self.counts(path)?)))
+    }
+}
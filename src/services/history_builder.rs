@@ -0,0 +1,40 @@
+use crate::models::{FutureHistory, GameState, HistoryEntry, Locale, StoryGraph};
+
+pub struct HistoryBuilder;
+
+impl HistoryBuilder {
+    pub fn build(graph: &StoryGraph, game_state: &GameState) -> FutureHistory {
+        let path = game_state.get_path();
+        let visited = graph.walk(path).unwrap_or_default();
+
+        let mut entries: Vec<HistoryEntry> = (1..=path.len())
+            .filter_map(|end| {
+                let room_path = &path[..end];
+                let content = &graph.node(visited.get(end)?)?.content;
+                let gem = game_state
+                    .gems
+                    .iter()
+                    .find(|collected| collected.path == room_path)
+                    .map(|collected| collected.gem.clone());
+                Some(HistoryEntry {
+                    path: room_path.to_string(),
+                    year: content.gem.as_ref().map(|gem| gem.year),
+                    title: content.title.clone(),
+                    story: content.story.clone(),
+                    gem,
+                })
+            })
+            .collect();
+
+        // Rooms are visited in path order, but the history reads by year
+        entries.sort_by_key(|entry| entry.year.unwrap_or(i32::MAX));
+
+        FutureHistory {
+            path: path.to_string(),
+            prologue: graph.prologue.clone(),
+            entries,
+            epilogue: graph.ending_at(path).clone(),
+            locale: Locale::default(),
+        }
+    }
+}
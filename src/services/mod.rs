@@ -1,5 +1,31 @@
 pub mod story_loader;
 pub mod path_navigator;
+pub mod story_validator;
+pub mod save_manager;
+pub mod story_api;
+pub mod history_builder;
+pub mod story_authoring;
+pub mod story_export;
+#[cfg(feature = "ssr")]
+pub mod story_watcher;
+#[cfg(feature = "ssr")]
+pub mod voting_rooms;
+#[cfg(feature = "ssr")]
+pub mod server_routes;
+pub mod choice_log;
 
 pub use story_loader::*;
-pub use path_navigator::*;
\ No newline at end of file
+pub use path_navigator::*;
+pub use story_validator::*;
+pub use save_manager::*;
+pub use story_api::*;
+pub use history_builder::*;
+pub use story_authoring::*;
+pub use story_export::*;
+#[cfg(feature = "ssr")]
+pub use story_watcher::*;
+#[cfg(feature = "ssr")]
+pub use voting_rooms::*;
+#[cfg(feature = "ssr")]
+pub use server_routes::*;
+pub use choice_log::*;
\ No newline at end of file
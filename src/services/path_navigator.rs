@@ -1,56 +1,103 @@
-use crate::models::{GameState, StoryData, StoryContent, ChoiceData};
+use crate::models::{GameState, StoryContent, StoryGraph, StoryNode, ChoiceData};
+use crate::utils::{AsciiTreeGenerator, TreeLayout, TreeOptions};
 
+// Walks a graph built elsewhere, so a loaded story is only built and measured once
 pub struct PathNavigator<'a> {
-    story_data: &'a StoryData,
+    graph: &'a StoryGraph,
 }
 
 impl<'a> PathNavigator<'a> {
-    pub fn new(story_data: &'a StoryData) -> Self {
-        Self { story_data }
+    pub fn new(graph: &'a StoryGraph) -> Self {
+        Self { graph }
     }
     
-    pub fn get_current_story(&self, game_state: &GameState) -> Option<&StoryContent> {
-        let path = game_state.get_path();
-        self.story_data.get_story_by_path(path)
+    pub fn graph(&self) -> &'a StoryGraph {
+        self.graph
     }
     
-    pub fn get_current_choice(&self, game_state: &GameState) -> Option<&ChoiceData> {
+    // Each step must be one of the options offered in the room it leaves
+    pub fn state_for_path(&self, path: &str) -> Option<GameState> {
+        let visited = self.graph.walk(path)?;
+        let mut game_state = GameState::from_path(path, self.graph.max_depth)?;
+        for (id, key) in visited.iter().zip(path.chars()) {
+            let option = self.graph.node(id)?.choice.as_ref()?.options.iter().find(|option| option.key == key)?;
+            game_state.apply_effects(&option.effects);
+        }
+        let here = visited.last().and_then(|id| self.graph.node(id))?;
+        if here.is_ending() && game_state.can_make_choice() {
+            game_state.finish();
+        }
+        Some(game_state)
+    }
+    
+    // Every path a player can take, gate first; graph loops stop at max_depth
+    pub fn reachable_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut frontier = vec![String::new()];
+        while let Some(path) = frontier.pop() {
+            let Some(game_state) = self.state_for_path(&path) else {
+                continue;
+            };
+            if let Some(choice) = self.get_current_choice(&game_state) {
+                frontier.extend(choice.options.iter().rev().map(|option| format!("{}{}", path, option.key)));
+            }
+            paths.push(path);
+        }
+        paths
+    }
+    
+    pub fn node_id(&self, game_state: &GameState) -> Option<&'a str> {
+        self.graph.walk(game_state.get_path())?.last().copied()
+    }
+    
+    pub fn get_current_story(&self, game_state: &GameState) -> Option<&'a StoryContent> {
+        self.graph.story_at(game_state.get_path())
+    }
+    
+    pub fn get_current_choice(&self, game_state: &GameState) -> Option<&'a ChoiceData> {
         if game_state.can_make_choice() {
-            self.story_data.get_choice_by_level(game_state.get_level())
+            let id = self.node_id(game_state)?;
+            self.graph.node(id)?.choice.as_ref()
         } else {
             None
         }
     }
     
-    pub fn get_final_story(&self) -> &StoryContent {
-        self.story_data.get_final_story()
+    pub fn get_final_story(&self, game_state: &GameState) -> &'a StoryContent {
+        self.graph.ending_at(game_state.get_path())
     }
     
-    pub fn generate_tree_visualization(&self, game_state: &GameState) -> String {
-        let mut tree = String::new();
-        tree.push_str("故事路径树:\n");
-        
-        for level in 0..=game_state.get_level() {
-            let prefix = "  ".repeat(level);
-            if level < game_state.get_level() {
-                let choice_char = game_state.get_path().chars().nth(level).unwrap_or('?');
-                let choice_name = match choice_char {
-                    'R' => "红色",
-                    'B' => "蓝色",
-                    _ => "未知",
-                };
-                tree.push_str(&format!("{}├─ Level {}: {}\n", prefix, level + 1, choice_name));
-            } else if level == game_state.get_level() && game_state.can_make_choice() {
-                tree.push_str(&format!("{}├─ Level {}: [当前选择]\n", prefix, level + 1));
-            }
+    pub fn get_node(&self, game_state: &GameState) -> StoryNode {
+        StoryNode {
+            path: game_state.get_path().to_string(),
+            node_id: self.node_id(game_state).unwrap_or_default().to_string(),
+            max_depth: self.graph.max_depth,
+            ending_count: self.graph.ending_count(),
+            intro: if game_state.get_level() == 0 {
+                self.graph.intro.clone()
+            } else {
+                None
+            },
+            story: self.get_current_story(game_state).cloned(),
+            choice: self.get_current_choice(game_state).cloned(),
+            final_story: if game_state.is_complete() {
+                Some(self.get_final_story(game_state).clone())
+            } else {
+                None
+            },
+            tree: self.generate_tree_visualization(game_state),
+            layout: TreeLayout::build(self.graph, game_state.get_path(), self.tree_options()),
+            axes: self.graph.axes().to_vec(),
+            world: game_state.world.clone(),
         }
-        
-        if game_state.is_complete() {
-            tree.push_str(&format!("{}└─ 完整路径: {}\n", 
-                "  ".repeat(game_state.get_level()), 
-                game_state.get_path()));
-        }
-        
-        tree
     }
-}
\ No newline at end of file
+    
+    // The sidebar tree: every branch one choice away from the path taken
+    pub fn generate_tree_visualization(&self, game_state: &GameState) -> String {
+        AsciiTreeGenerator::generate_full_tree(self.graph, game_state.get_path(), self.tree_options())
+    }
+    
+    fn tree_options(&self) -> TreeOptions {
+        TreeOptions::new(self.graph.max_depth)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::models::{GameState, Locale, LoopMemory, SaveSlot, TextSpeed};
+use gloo_storage::{LocalStorage, Storage};
+use thiserror::Error;
+
+const STORAGE_KEY: &str = "l3_story_game.saves";
+const MEMORY_KEY: &str = "l3_story_game.memory";
+const TEXT_SPEED_KEY: &str = "l3_story_game.text_speed";
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] gloo_storage::errors::StorageError),
+    #[error("Save slot not found: {0}")]
+    NotFound(String),
+    #[error("Save slot {0} was written by an incompatible version")]
+    Incompatible(String),
+    #[error("Save slot name must not be empty")]
+    EmptyName,
+    #[error("Save slot could not be encoded: {0}")]
+    Encode(#[from] serde_json::Error),
+    #[error("Stored save slots could not be read: {0}")]
+    Unreadable(String),
+}
+
+pub struct SaveManager;
+
+impl SaveManager {
+    // Stored slots stay raw JSON, so saving or deleting one never rewrites the others;
+    // a list that can't be read is left alone rather than replaced by an empty one
+    fn stored_slots() -> Result<Vec<serde_json::Value>, SaveError> {
+        let raw = LocalStorage::raw()
+            .get_item(STORAGE_KEY)
+            .map_err(|e| SaveError::Unreadable(format!("{:?}", e)))?;
+        SaveSlot::parse_stored(raw.as_deref()).map_err(|e| SaveError::Unreadable(e.to_string()))
+    }
+
+    pub fn list_slots() -> Result<Vec<SaveSlot>, SaveError> {
+        let mut slots: Vec<SaveSlot> = Self::stored_slots()?.iter().map(SaveSlot::from_value).collect();
+        slots.sort_by(|a, b| b.saved_at.total_cmp(&a.saved_at));
+        Ok(slots)
+    }
+
+    pub fn save(name: &str, game_state: &GameState) -> Result<SaveSlot, SaveError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(SaveError::EmptyName);
+        }
+
+        let slot = SaveSlot::new(name, js_sys::Date::now(), game_state.clone());
+        let mut slots = Self::stored_slots()?;
+        slots.retain(|s| SaveSlot::stored_name(s) != Some(name));
+        slots.push(serde_json::to_value(&slot)?);
+        LocalStorage::set(STORAGE_KEY, &slots)?;
+
+        Ok(slot)
+    }
+
+    pub fn load(name: &str) -> Result<GameState, SaveError> {
+        let slot = Self::list_slots()?
+            .into_iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| SaveError::NotFound(name.to_string()))?;
+
+        if !slot.is_compatible() {
+            return Err(SaveError::Incompatible(name.to_string()));
+        }
+
+        Ok(slot.state)
+    }
+
+    pub fn delete(name: &str) -> Result<(), SaveError> {
+        let mut slots = Self::stored_slots()?;
+        slots.retain(|s| SaveSlot::stored_name(s) != Some(name));
+        LocalStorage::set(STORAGE_KEY, &slots)?;
+        Ok(())
+    }
+
+    pub fn load_memory() -> LoopMemory {
+        LocalStorage::get(MEMORY_KEY).unwrap_or_default()
+    }
+
+    pub fn store_memory(memory: &LoopMemory) -> Result<(), SaveError> {
+        LocalStorage::set(MEMORY_KEY, memory)?;
+        Ok(())
+    }
+
+    pub fn load_text_speed() -> TextSpeed {
+        LocalStorage::get(TEXT_SPEED_KEY).unwrap_or_default()
+    }
+
+    pub fn store_text_speed(speed: TextSpeed) -> Result<(), SaveError> {
+        LocalStorage::set(TEXT_SPEED_KEY, speed)?;
+        Ok(())
+    }
+
+    pub fn format_timestamp(saved_at: f64, locale: Locale) -> String {
+        let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(saved_at));
+        String::from(date.to_locale_string(locale.code(), &wasm_bindgen::JsValue::UNDEFINED))
+    }
+}
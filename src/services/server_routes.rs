@@ -0,0 +1,66 @@
+use crate::models::{self, Locale};
+use crate::services::{ChoiceLog, SharedStoryData, VoteSocket, VotingRooms};
+use axum::{
+    body::Body,
+    extract::{ws::WebSocketUpgrade, Extension, Path, Query},
+    http::{Request, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use leptos::provide_context;
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+// Server functions get the story and the choice log from context
+pub async fn server_fn_handler(
+    Extension(story): Extension<SharedStoryData>,
+    Extension(choices): Extension<ChoiceLog>,
+    req: Request<Body>,
+) -> impl IntoResponse {
+    leptos_axum::handle_server_fns_with_context(
+        move || {
+            provide_context(story.clone());
+            provide_context(choices.clone());
+        },
+        req,
+    )
+    .await
+}
+
+// Tells open pages that the story was reloaded so they refetch the current node
+pub async fn story_events_handler(
+    Extension(story): Extension<SharedStoryData>,
+) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
+    let updates = futures::stream::unfold(story.subscribe(), |mut revision| async move {
+        revision.changed().await.ok()?;
+        let event = Event::default().event("story-updated").data(revision.borrow_and_update().to_string());
+        Some((Ok(event), revision))
+    });
+    Sse::new(updates).keep_alive(KeepAlive::default())
+}
+
+// The presenter's screen opens a voting room; `?lang=` picks the story pack it votes on
+pub async fn vote_host_handler(
+    ws: WebSocketUpgrade,
+    Query(query): Query<HashMap<String, String>>,
+    Extension(story): Extension<SharedStoryData>,
+    Extension(rooms): Extension<VotingRooms>,
+) -> impl IntoResponse {
+    let locale = query.get("lang").and_then(|code| Locale::from_code(code)).unwrap_or_default();
+    ws.on_upgrade(move |socket| VoteSocket::host(socket, story, rooms, locale))
+}
+
+// Phones join a room by its code
+pub async fn vote_join_handler(
+    ws: WebSocketUpgrade,
+    Path(code): Path<String>,
+    Extension(story): Extension<SharedStoryData>,
+    Extension(rooms): Extension<VotingRooms>,
+) -> impl IntoResponse {
+    match models::normalize_join_code(&code).and_then(|code| rooms.get(&code)) {
+        Some(room) => ws.on_upgrade(move |socket| VoteSocket::audience(socket, story, room)).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
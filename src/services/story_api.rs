@@ -0,0 +1,153 @@
+use leptos::*;
+use leptos::server_fn::codec::Json;
+use crate::models::{ChoiceStats, FutureHistory, GameState, Locale, StoryNode};
+use crate::models::{StoryData, StoryGraph, StoryTranslation};
+use crate::services::PathNavigator;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
+
+pub struct StoryPack {
+    pub data: StoryData,
+    pub graph: StoryGraph,
+}
+
+impl StoryPack {
+    pub fn new(data: StoryData) -> Self {
+        Self {
+            graph: StoryGraph::from_story_data(&data),
+            data,
+        }
+    }
+
+    pub fn navigator(&self) -> PathNavigator<'_> {
+        PathNavigator::new(&self.graph)
+    }
+}
+
+type StoryPacks = HashMap<Locale, Arc<StoryPack>>;
+
+// Story data handed to server functions through context; each locale's graph is
+// built once per load, and a reload swaps every pack at once
+#[derive(Clone)]
+pub struct SharedStoryData {
+    packs: Arc<RwLock<Arc<StoryPacks>>>,
+    revision: Arc<watch::Sender<u64>>,
+}
+
+impl SharedStoryData {
+    pub fn new(story_data: StoryData, translations: HashMap<Locale, StoryTranslation>) -> Self {
+        let (revision, _) = watch::channel(0);
+        Self {
+            packs: Arc::new(RwLock::new(Arc::new(Self::build_packs(story_data, translations)))),
+            revision: Arc::new(revision),
+        }
+    }
+
+    fn build_packs(story_data: StoryData, translations: HashMap<Locale, StoryTranslation>) -> StoryPacks {
+        let mut packs: StoryPacks = translations
+            .iter()
+            .map(|(locale, translation)| (*locale, Arc::new(StoryPack::new(story_data.localized(translation)))))
+            .collect();
+        packs.insert(Locale::default(), Arc::new(StoryPack::new(story_data)));
+        packs
+    }
+
+    pub fn pack(&self, locale: Locale) -> Arc<StoryPack> {
+        let packs = self.packs.read().unwrap_or_else(|e| e.into_inner()).clone();
+        packs
+            .get(&locale)
+            .unwrap_or_else(|| &packs[&Locale::default()])
+            .clone()
+    }
+
+    // The default locale comes first
+    pub fn locales(&self) -> Vec<Locale> {
+        let packs = self.packs.read().unwrap_or_else(|e| e.into_inner()).clone();
+        Locale::ALL.into_iter().filter(|locale| packs.contains_key(locale)).collect()
+    }
+
+    // Packs are built before taking the lock, so readers never see a half-loaded story
+    pub fn replace(&self, story_data: StoryData, translations: HashMap<Locale, StoryTranslation>) {
+        let packs = Arc::new(Self::build_packs(story_data, translations));
+        *self.packs.write().unwrap_or_else(|e| e.into_inner()) = packs;
+        self.revision.send_modify(|revision| *revision += 1);
+    }
+
+    pub fn revision(&self) -> u64 {
+        *self.revision.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.revision.subscribe()
+    }
+}
+
+// Paths that don't name a node resolve to `None` so the page can render a 404
+#[server(GetStoryNode, "/api")]
+pub async fn get_story_node(path: String, locale: Locale) -> Result<Option<StoryNode>, ServerFnError> {
+    let shared = use_context::<SharedStoryData>()
+        .ok_or_else(|| ServerFnError::new("Story data is not loaded"))?;
+    let pack = shared.pack(locale);
+    let navigator = pack.navigator();
+
+    Ok(navigator.state_for_path(&path).map(|game_state| navigator.get_node(&game_state)))
+}
+
+#[server(name = CompileHistory, prefix = "/api", input = Json)]
+pub async fn compile_history(game_state: GameState, locale: Locale) -> Result<FutureHistory, ServerFnError> {
+    use crate::models::CollectedGem;
+    use crate::services::HistoryBuilder;
+
+    let shared = use_context::<SharedStoryData>()
+        .ok_or_else(|| ServerFnError::new("Story data is not loaded"))?;
+    let pack = shared.pack(locale);
+    let navigator = pack.navigator();
+    let graph = navigator.graph();
+    let path = game_state.get_path();
+    let mut state = navigator
+        .state_for_path(path)
+        .ok_or_else(|| ServerFnError::new(format!("Invalid story path: {}", path)))?;
+
+    // Only trust which rooms were looted; the gem contents come from the server copy
+    let gems: Vec<CollectedGem> = game_state
+        .gems
+        .iter()
+        .filter_map(|collected| {
+            let content = graph.story_at(&collected.path)?;
+            Some(CollectedGem {
+                path: collected.path.clone(),
+                gem: content.gem.clone()?,
+            })
+        })
+        .collect();
+    state.restore_gems(&gems);
+
+    Ok(FutureHistory {
+        locale,
+        ..HistoryBuilder::build(graph, &state)
+    })
+}
+
+// Counts the decision a player just made and answers with how everyone chose there;
+// the log only keeps totals, and option keys are the same in every locale
+#[server(name = RecordChoice, prefix = "/api", input = Json)]
+pub async fn record_choice(path: String, key: char, locale: Locale) -> Result<Option<ChoiceStats>, ServerFnError> {
+    use crate::services::ChoiceLog;
+
+    let shared = use_context::<SharedStoryData>()
+        .ok_or_else(|| ServerFnError::new("Story data is not loaded"))?;
+    let log = use_context::<ChoiceLog>()
+        .ok_or_else(|| ServerFnError::new("Choice statistics are unavailable: no choice log was provided"))?;
+    let pack = shared.pack(locale);
+
+    // SQLite blocks, so keep it off the async workers
+    tokio::task::spawn_blocking(move || {
+        let navigator = pack.navigator();
+        log.record(&navigator, &path, key)?;
+        log.stats(&navigator, &path)
+    })
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
@@ -0,0 +1,208 @@
+use crate::models::{Scenario, StoryData, StoryGraph};
+use crate::services::{PathNavigator, StoryValidator, MIN_STORY_CHARS};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomStats {
+    pub key: String,
+    pub level: usize,
+    pub title: String,
+    pub chars: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelStats {
+    pub level: usize,
+    pub rooms: usize,
+    pub total_chars: usize,
+    pub min_chars: usize,
+    pub max_chars: usize,
+    pub short_rooms: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndingStats {
+    pub path: String,
+    pub title: String,
+    pub scenario: Option<Scenario>,
+}
+
+// Character counts for every reachable room, grouped by how deep it sits, and
+// the scenario each complete run ends in
+#[derive(Debug, Clone, Default)]
+pub struct StoryStats {
+    pub rooms: Vec<RoomStats>,
+    pub levels: Vec<LevelStats>,
+    pub endings: Vec<EndingStats>,
+}
+
+impl StoryStats {
+    pub fn collect(story_data: &StoryData) -> Self {
+        let graph = StoryGraph::from_story_data(story_data);
+        let mut rooms: Vec<RoomStats> = graph
+            .depths()
+            .into_iter()
+            .filter_map(|(id, level)| {
+                let content = &graph.node(id)?.content;
+                Some(RoomStats {
+                    key: story_data.section_key(id),
+                    level,
+                    title: content.title.clone(),
+                    chars: StoryValidator::story_chars(&content.story),
+                })
+            })
+            .collect();
+        rooms.sort_by(|a, b| {
+            (a.level, a.key.len(), &a.key).cmp(&(b.level, b.key.len(), &b.key))
+        });
+
+        let mut levels: Vec<LevelStats> = Vec::new();
+        for room in &rooms {
+            match levels.last_mut().filter(|stats| stats.level == room.level) {
+                Some(stats) => {
+                    stats.rooms += 1;
+                    stats.total_chars += room.chars;
+                    stats.min_chars = stats.min_chars.min(room.chars);
+                    stats.max_chars = stats.max_chars.max(room.chars);
+                }
+                None => levels.push(LevelStats {
+                    level: room.level,
+                    rooms: 1,
+                    total_chars: room.chars,
+                    min_chars: room.chars,
+                    max_chars: room.chars,
+                    short_rooms: 0,
+                }),
+            }
+            if room.chars < MIN_STORY_CHARS {
+                levels.last_mut().unwrap().short_rooms += 1;
+            }
+        }
+
+        Self { rooms, levels, endings: Self::endings(story_data) }
+    }
+
+    // One entry per path, so an ending shared by two runs counts twice
+    fn endings(story_data: &StoryData) -> Vec<EndingStats> {
+        let graph = StoryGraph::from_story_data(story_data);
+        let navigator = PathNavigator::new(&graph);
+        navigator
+            .reachable_paths()
+            .into_iter()
+            .filter_map(|path| {
+                let game_state = navigator.state_for_path(&path)?;
+                if !game_state.is_complete() {
+                    return None;
+                }
+                let story = navigator.get_current_story(&game_state)?;
+                Some(EndingStats {
+                    title: story.title.clone(),
+                    scenario: story.scenario,
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    pub fn total_chars(&self) -> usize {
+        self.rooms.iter().map(|room| room.chars).sum()
+    }
+
+    // Every scenario in the book's order, then untagged endings if there are any
+    pub fn scenario_counts(&self) -> Vec<(Option<Scenario>, usize)> {
+        let count = |scenario: Option<Scenario>| self.endings.iter().filter(|ending| ending.scenario == scenario).count();
+        let mut counts: Vec<_> = Scenario::ALL.into_iter().map(|scenario| (Some(scenario), count(Some(scenario)))).collect();
+        let untagged = count(None);
+        if untagged > 0 {
+            counts.push((None, untagged));
+        }
+        counts
+    }
+}
+
+pub struct StoryScaffold;
+
+impl StoryScaffold {
+    // Empty TOML tables for the branches below `path` that the story doesn't
+    // have yet, ready to append; `None` when `path` doesn't lead anywhere
+    pub fn missing_sections(story_data: &StoryData, path: &str) -> Option<String> {
+        if story_data.is_graph() {
+            Self::missing_nodes(story_data, path)
+        } else {
+            Self::missing_paths(story_data, path)
+        }
+    }
+
+    fn missing_paths(story_data: &StoryData, under: &str) -> Option<String> {
+        let expected = StoryValidator::expected_paths(&StoryValidator::choice_levels(story_data));
+        if !under.is_empty() && !expected.iter().any(|path| path == under) {
+            return None;
+        }
+
+        let mut sections = String::new();
+        for level in under.chars().count() + 1..=story_data.path_depth() {
+            if story_data.get_choice_by_level(level).is_none() {
+                sections.push_str(&format!(
+                    "\n[FM_CHOICE.{}]\ntitle = \"\"\nstory = \"\"\nred = \"\"\nblue = \"\"\n",
+                    level
+                ));
+            }
+        }
+        for path in expected {
+            if path.starts_with(under) && !story_data.fm_story.contains_key(&path) {
+                sections.push_str(&format!(
+                    "\n[FM_STORY.{}]\ntitle = \"\"\nstory = \"\"\ngem = {{ name = \"\", year = 0, description = \"\" }}\n",
+                    path
+                ));
+            }
+        }
+        Some(sections)
+    }
+
+    // Graph rooms are found by following options from the node at `path`
+    fn missing_nodes(story_data: &StoryData, path: &str) -> Option<String> {
+        let graph = StoryGraph::from_story_data(story_data);
+        let mut missing = BTreeSet::new();
+        let from = if path.is_empty() {
+            if graph.node(&graph.start).is_none() {
+                missing.insert(graph.start.clone());
+            }
+            graph.start.as_str()
+        } else {
+            *graph.walk(path)?.last()?
+        };
+
+        let mut seen = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(id) = queue.pop_front() {
+            let targets = graph
+                .node(id)
+                .and_then(|node| node.choice.as_ref())
+                .into_iter()
+                .flat_map(|choice| choice.options.iter())
+                .filter_map(|option| option.target.as_deref());
+            for target in targets {
+                if graph.node(target).is_none() {
+                    missing.insert(target.to_string());
+                } else if seen.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+
+        Some(
+            missing
+                .into_iter()
+                .map(|id| format!("\n[FM_NODE.{}]\ntitle = \"\"\nstory = \"\"\n", Self::table_key(&id)))
+                .collect(),
+        )
+    }
+
+    fn table_key(id: &str) -> String {
+        if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            id.to_string()
+        } else {
+            toml::Value::String(id.to_string()).to_string()
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::models::{StoryData, StoryGraph};
+use crate::services::{Severity, StoryValidator, MIN_STORY_CHARS};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeColoring {
+    #[default]
+    Plain,
+    Length,
+    Validation,
+}
+
+impl NodeColoring {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(NodeColoring::Plain),
+            "length" => Some(NodeColoring::Length),
+            "validation" => Some(NodeColoring::Validation),
+            _ => None,
+        }
+    }
+}
+
+// Fill colors shared by both formats, keyed by the class a node falls into
+const CLASSES: [(&str, &str); 5] = [
+    ("ok", "#d4edda"),
+    ("short", "#fde2c8"),
+    ("empty", "#f5a3a3"),
+    ("warning", "#fde2c8"),
+    ("error", "#f5a3a3"),
+];
+
+struct ExportNode {
+    key: String,
+    label: String,
+    class: Option<&'static str>,
+    missing: bool,
+}
+
+struct ExportEdge {
+    from: usize,
+    to: usize,
+    label: String,
+    color: String,
+}
+
+// Rooms in reading order: reachable ones by depth, then anything left over
+struct StoryDiagram {
+    nodes: Vec<ExportNode>,
+    edges: Vec<ExportEdge>,
+}
+
+impl StoryDiagram {
+    fn build(story_data: &StoryData, coloring: NodeColoring) -> Self {
+        let graph = StoryGraph::from_story_data(story_data);
+        let depths = graph.depths();
+        let mut ids: Vec<&str> = graph.nodes.keys().map(String::as_str).collect();
+        ids.sort_by_key(|id| (depths.get(id).copied().unwrap_or(usize::MAX), id.len(), *id));
+
+        let severities = match coloring {
+            NodeColoring::Validation => Self::severities(story_data),
+            _ => HashMap::new(),
+        };
+        let mut nodes: Vec<ExportNode> = ids
+            .iter()
+            .map(|id| {
+                let key = story_data.section_key(id);
+                let content = &graph.nodes[*id].content;
+                let class = match coloring {
+                    NodeColoring::Plain => None,
+                    NodeColoring::Length => Some(match StoryValidator::story_chars(&content.story) {
+                        0 => "empty",
+                        chars if chars < MIN_STORY_CHARS => "short",
+                        _ => "ok",
+                    }),
+                    NodeColoring::Validation => {
+                        // Path stories share one FM_CHOICE.n across every room at level n-1
+                        let choice_key = (!story_data.is_graph()).then(|| format!("FM_CHOICE.{}", id.chars().count() + 1));
+                        let severity = severities
+                            .iter()
+                            .filter(|(diagnostic_key, _)| {
+                                Self::belongs_to(diagnostic_key, &key)
+                                    || choice_key.as_deref().is_some_and(|choice_key| *diagnostic_key == choice_key)
+                            })
+                            .map(|(_, severity)| *severity)
+                            .max();
+                        Some(match severity {
+                            Some(Severity::Error) => "error",
+                            Some(Severity::Warning) => "warning",
+                            None => "ok",
+                        })
+                    }
+                };
+                let label = if id.is_empty() { key.clone() } else { id.to_string() };
+                ExportNode {
+                    label: format!("{}\n{}", label, content.title),
+                    key,
+                    class,
+                    missing: false,
+                }
+            })
+            .collect();
+
+        let mut index: HashMap<String, usize> = ids.iter().enumerate().map(|(i, id)| (id.to_string(), i)).collect();
+        let mut edges = Vec::new();
+        for (from, id) in ids.iter().enumerate() {
+            let Some(choice) = graph.nodes[*id].choice.as_ref() else {
+                continue;
+            };
+            for option in &choice.options {
+                let target = option.target.clone().unwrap_or_default();
+                // Options that lead nowhere still get drawn, so the gap is visible
+                let to = *index.entry(target.clone()).or_insert_with(|| {
+                    nodes.push(ExportNode {
+                        key: story_data.section_key(&target),
+                        label: format!("✗ {}", target),
+                        class: Some("error"),
+                        missing: true,
+                    });
+                    nodes.len() - 1
+                });
+                edges.push(ExportEdge {
+                    from,
+                    to,
+                    label: option.label.clone(),
+                    color: option.color.clone(),
+                });
+            }
+        }
+
+        Self { nodes, edges }
+    }
+
+    fn severities(story_data: &StoryData) -> HashMap<String, Severity> {
+        let mut severities: HashMap<String, Severity> = HashMap::new();
+        for diagnostic in StoryValidator::validate(story_data).diagnostics {
+            if let Some(key) = diagnostic.key() {
+                let severity = severities.entry(key).or_insert(diagnostic.severity);
+                *severity = (*severity).max(diagnostic.severity);
+            }
+        }
+        severities
+    }
+
+    // `FM_NODE.a.choice` is about `FM_NODE.a`, but `FM_STORY.RR` isn't about `FM_STORY.R`
+    fn belongs_to(diagnostic_key: &str, key: &str) -> bool {
+        diagnostic_key
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    }
+
+    fn classes(&self) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+        CLASSES
+            .into_iter()
+            .filter(|(class, _)| self.nodes.iter().any(|node| node.class == Some(*class)))
+    }
+}
+
+pub struct StoryExporter;
+
+impl StoryExporter {
+    pub fn to_dot(story_data: &StoryData, coloring: NodeColoring) -> String {
+        let diagram = StoryDiagram::build(story_data, coloring);
+        let fills: HashMap<&str, &str> = CLASSES.into_iter().collect();
+        let mut dot = String::new();
+
+        dot.push_str("digraph story {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=\"rounded,filled\", fillcolor=\"#ffffff\", fontname=\"sans-serif\"];\n");
+        dot.push_str("    edge [fontname=\"sans-serif\", fontsize=10];\n\n");
+
+        for node in &diagram.nodes {
+            let mut attrs = vec![format!("label={}", dot_string(&node.label))];
+            if let Some(class) = node.class {
+                attrs.push(format!("fillcolor=\"{}\"", fills[class]));
+            }
+            if node.missing {
+                attrs.push("style=\"rounded,filled,dashed\"".to_string());
+            }
+            dot.push_str(&format!("    {} [{}];\n", dot_string(&node.key), attrs.join(", ")));
+        }
+        dot.push('\n');
+
+        for edge in &diagram.edges {
+            let mut attrs = vec![format!("label={}", dot_string(&edge.label))];
+            if !edge.color.is_empty() {
+                attrs.push(format!("color={}", dot_string(&edge.color)));
+            }
+            dot.push_str(&format!(
+                "    {} -> {} [{}];\n",
+                dot_string(&diagram.nodes[edge.from].key),
+                dot_string(&diagram.nodes[edge.to].key),
+                attrs.join(", ")
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_mermaid(story_data: &StoryData, coloring: NodeColoring) -> String {
+        let diagram = StoryDiagram::build(story_data, coloring);
+        let mut mermaid = String::from("flowchart LR\n");
+
+        // Mermaid IDs can't hold dots or CJK, so nodes are numbered in reading order
+        for (i, node) in diagram.nodes.iter().enumerate() {
+            mermaid.push_str(&format!("    n{}[\"{}\"]\n", i, mermaid_text(&node.label)));
+        }
+        for edge in &diagram.edges {
+            mermaid.push_str(&format!(
+                "    n{} -->|\"{}\"| n{}\n",
+                edge.from,
+                mermaid_text(&edge.label),
+                edge.to
+            ));
+        }
+        for (i, edge) in diagram.edges.iter().enumerate() {
+            if !edge.color.is_empty() {
+                mermaid.push_str(&format!("    linkStyle {} stroke:{}\n", i, edge.color));
+            }
+        }
+
+        for (class, fill) in diagram.classes() {
+            let members: Vec<String> = diagram
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| node.class == Some(class))
+                .map(|(i, _)| format!("n{}", i))
+                .collect();
+            mermaid.push_str(&format!("    classDef {} fill:{}\n", class, fill));
+            mermaid.push_str(&format!("    class {} {}\n", members.join(","), class));
+        }
+        for (i, node) in diagram.nodes.iter().enumerate() {
+            if node.missing {
+                mermaid.push_str(&format!("    style n{} stroke-dasharray: 5 5\n", i));
+            }
+        }
+
+        mermaid
+    }
+}
+
+fn dot_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+// Mermaid reads `#name;` as an entity, so `#` itself has to be escaped too
+fn mermaid_text(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '#' => "#35;".to_string(),
+            '"' => "#quot;".to_string(),
+            '<' => "#lt;".to_string(),
+            '>' => "#gt;".to_string(),
+            '\n' => "<br/>".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
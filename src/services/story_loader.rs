@@ -1,5 +1,7 @@
-use crate::models::StoryData;
-use std::path::Path;
+use crate::models::{Locale, StoryData, StoryTranslation};
+use std::collections::HashMap;
+use crate::services::{StoryValidator, ValidationReport};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,6 +12,8 @@ pub enum StoryLoaderError {
     Toml(#[from] toml::de::Error),
     #[error("Story file not found: {0}")]
     NotFound(String),
+    #[error("Story file failed validation with {} error(s)", .0.errors().count())]
+    Invalid(ValidationReport),
 }
 
 pub struct StoryLoader;
@@ -28,6 +32,40 @@ impl StoryLoader {
         Ok(story_data)
     }
     
+    pub fn load_validated<P: AsRef<Path>>(path: P) -> Result<(StoryData, ValidationReport), StoryLoaderError> {
+        let path = path.as_ref();
+        
+        if !path.exists() {
+            return Err(StoryLoaderError::NotFound(path.display().to_string()));
+        }
+        
+        let content = std::fs::read_to_string(path)?;
+        match StoryValidator::validate_str(&content) {
+            (Some(story_data), report) if report.is_valid() => Ok((story_data, report)),
+            (_, report) => Err(StoryLoaderError::Invalid(report)),
+        }
+    }
+    
+    // `FM_STORY.toml` is the default locale; `FM_STORY.en.toml` next to it is the English pack
+    pub fn translation_path<P: AsRef<Path>>(path: P, locale: Locale) -> PathBuf {
+        let path = path.as_ref();
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("FM_STORY");
+        path.with_file_name(format!("{}.{}.toml", stem, locale.code()))
+    }
+    
+    pub fn load_translations<P: AsRef<Path>>(path: P) -> Result<HashMap<Locale, StoryTranslation>, StoryLoaderError> {
+        let mut translations = HashMap::new();
+        for locale in Locale::ALL.into_iter().filter(|locale| *locale != Locale::default()) {
+            let translation_path = Self::translation_path(&path, locale);
+            if !translation_path.exists() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&translation_path)?;
+            translations.insert(locale, toml::from_str(&content)?);
+        }
+        Ok(translations)
+    }
+    
     pub fn load_default() -> Result<StoryData, StoryLoaderError> {
         let default_path = "../docs/FM_STORY.toml";
         Self::load_from_file(default_path)
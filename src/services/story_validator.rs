@@ -0,0 +1,546 @@
+use crate::models::{ChoiceData, ChoiceType, Scenario, StoryContent, StoryData, StoryGraph};
+use crate::services::PathNavigator;
+use serde::Deserialize;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+pub const MIN_STORY_CHARS: usize = 420;
+
+const REQUIRED_SECTIONS: [&str; 2] = ["FM_START", "FM_NOEND"];
+const PATH_SECTIONS: [&str; 2] = ["FM_CHOICE", "FM_STORY"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    Syntax(String),
+    MissingSection(String),
+    InvalidField { key: String, field: String },
+    MissingPath(String),
+    ExtraPath(String),
+    MissingChoiceLevel(usize),
+    UnusedChoiceLevel(String),
+    StoryTooShort { key: String, chars: usize },
+    MissingGem(String),
+    TooFewOptions(String),
+    InvalidOption { key: String, option: char },
+    DuplicateOption { key: String, option: char },
+    MissingNode(String),
+    MissingTarget { key: String, option: char },
+    UnknownTarget { key: String, target: String },
+    UnreachableNode(String),
+    MissingScenario(String),
+    UnknownScenario { key: String, scenario: String },
+    UnusedEnding(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    fn error(kind: DiagnosticKind) -> Self {
+        Self { severity: Severity::Error, kind }
+    }
+
+    fn warning(kind: DiagnosticKind) -> Self {
+        Self { severity: Severity::Warning, kind }
+    }
+
+    // The TOML table the problem was found in, if it's about a single one
+    pub fn key(&self) -> Option<String> {
+        match &self.kind {
+            DiagnosticKind::Syntax(_) => None,
+            DiagnosticKind::MissingSection(name) => Some(name.clone()),
+            DiagnosticKind::InvalidField { key, .. }
+            | DiagnosticKind::StoryTooShort { key, .. }
+            | DiagnosticKind::MissingGem(key)
+            | DiagnosticKind::MissingScenario(key)
+            | DiagnosticKind::UnknownScenario { key, .. }
+            | DiagnosticKind::UnusedEnding(key)
+            | DiagnosticKind::TooFewOptions(key)
+            | DiagnosticKind::InvalidOption { key, .. }
+            | DiagnosticKind::DuplicateOption { key, .. }
+            | DiagnosticKind::MissingTarget { key, .. }
+            | DiagnosticKind::UnknownTarget { key, .. } => Some(key.clone()),
+            DiagnosticKind::MissingPath(path) | DiagnosticKind::ExtraPath(path) => Some(format!("FM_STORY.{}", path)),
+            DiagnosticKind::MissingChoiceLevel(level) => Some(format!("FM_CHOICE.{}", level)),
+            DiagnosticKind::UnusedChoiceLevel(level) => Some(format!("FM_CHOICE.{}", level)),
+            DiagnosticKind::MissingNode(id) | DiagnosticKind::UnreachableNode(id) => Some(format!("FM_NODE.{}", id)),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match &self.kind {
+            DiagnosticKind::Syntax(msg) => write!(f, "{}: TOML syntax: {}", level, msg),
+            DiagnosticKind::MissingSection(name) => write!(f, "{}: missing section [{}]", level, name),
+            DiagnosticKind::InvalidField { key, field } => {
+                write!(f, "{}: {} has no string field `{}`", level, key, field)
+            }
+            DiagnosticKind::MissingPath(path) => write!(f, "{}: missing FM_STORY.{}", level, path),
+            DiagnosticKind::ExtraPath(path) => write!(f, "{}: unexpected FM_STORY.{}", level, path),
+            DiagnosticKind::MissingChoiceLevel(n) => write!(f, "{}: missing FM_CHOICE.{}", level, n),
+            DiagnosticKind::UnusedChoiceLevel(key) => write!(f, "{}: unused FM_CHOICE.{}", level, key),
+            DiagnosticKind::StoryTooShort { key, chars } => write!(
+                f,
+                "{}: {} has {} characters, expected at least {}",
+                level, key, chars, MIN_STORY_CHARS
+            ),
+            DiagnosticKind::MissingGem(key) => write!(f, "{}: {} has no time gem", level, key),
+            DiagnosticKind::TooFewOptions(key) => write!(f, "{}: {} needs at least two options", level, key),
+            DiagnosticKind::InvalidOption { key, option } => {
+                write!(f, "{}: {} option key `{}` must be an uppercase letter or digit", level, key, option)
+            }
+            DiagnosticKind::DuplicateOption { key, option } => {
+                write!(f, "{}: {} lists option `{}` more than once", level, key, option)
+            }
+            DiagnosticKind::MissingNode(id) => write!(f, "{}: missing start node FM_NODE.{}", level, id),
+            DiagnosticKind::MissingTarget { key, option } => {
+                write!(f, "{}: {} option `{}` has no target", level, key, option)
+            }
+            DiagnosticKind::UnknownTarget { key, target } => {
+                write!(f, "{}: {} leads to unknown node `{}`", level, key, target)
+            }
+            DiagnosticKind::UnreachableNode(id) => write!(f, "{}: FM_NODE.{} can't be reached from the start", level, id),
+            DiagnosticKind::MissingScenario(key) => write!(f, "{}: ending {} has no Life 3.0 scenario", level, key),
+            DiagnosticKind::UnknownScenario { key, scenario } => {
+                write!(f, "{}: {} names unknown scenario `{}`", level, key, scenario)
+            }
+            DiagnosticKind::UnusedEnding(key) => {
+                write!(f, "{}: {} matches no ending path or scenario", level, key)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        !self.diagnostics.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Warning)
+    }
+
+    fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+pub struct StoryValidator;
+
+impl StoryValidator {
+    // One entry per level, holding the option keys offered there
+    pub fn expected_paths(levels: &[Vec<ChoiceType>]) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut frontier = vec![String::new()];
+
+        for choices in levels {
+            let mut next = Vec::new();
+            for prefix in &frontier {
+                for choice in choices {
+                    let mut path = prefix.clone();
+                    path.push(choice.as_char());
+                    next.push(path);
+                }
+            }
+            paths.extend(next.iter().cloned());
+            frontier = next;
+        }
+
+        paths
+    }
+
+    // A missing choice level is reported on its own; assume the classic
+    // red/blue split there so the paths below it are still checked
+    pub fn choice_levels(story_data: &StoryData) -> Vec<Vec<ChoiceType>> {
+        (1..=story_data.path_depth())
+            .map(|level| {
+                story_data
+                    .get_choice_by_level(level)
+                    .map(|choice| choice.choice_types())
+                    .unwrap_or_else(|| vec![ChoiceType::Red, ChoiceType::Blue])
+            })
+            .collect()
+    }
+
+    pub fn validate(story_data: &StoryData) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        // Rooms with the flag set are endings
+        let mut rooms: Vec<(String, &StoryContent, bool)> = if story_data.is_graph() {
+            Self::check_graph(&mut report, story_data);
+            story_data
+                .fm_node
+                .iter()
+                .map(|(id, node)| (format!("FM_NODE.{}", id), &node.content, node.is_ending()))
+                .collect()
+        } else {
+            Self::check_paths(&mut report, story_data);
+            let max_depth = story_data.path_depth();
+            story_data
+                .fm_story
+                .iter()
+                .map(|(path, content)| (format!("FM_STORY.{}", path), content, path.chars().count() == max_depth))
+                .collect()
+        };
+
+        // Every run opens on FM_START and may end on FM_NOEND, so they get the rooms' minimum too
+        Self::check_length(&mut report, "FM_START", &story_data.fm_start.story);
+        Self::check_length(&mut report, "FM_NOEND", &story_data.fm_noend.story);
+
+        rooms.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+        for (key, content, ending) in rooms {
+            Self::check_length(&mut report, &key, &content.story);
+            if content.gem.is_none() {
+                report.push(Diagnostic::warning(DiagnosticKind::MissingGem(key.clone())));
+            }
+            if ending && content.scenario.is_none() {
+                report.push(Diagnostic::warning(DiagnosticKind::MissingScenario(key)));
+            }
+        }
+        Self::check_endings(&mut report, story_data);
+
+        report
+    }
+
+    // FM_ENDING.paths entries must name a path that finishes a run, and
+    // FM_ENDING.scenarios entries a scenario some finished run ends in
+    fn check_endings(report: &mut ValidationReport, story_data: &StoryData) {
+        if story_data.fm_ending.is_empty() {
+            return;
+        }
+        let graph = StoryGraph::from_story_data(story_data);
+        let navigator = PathNavigator::new(&graph);
+        let mut paths = BTreeSet::new();
+        let mut scenarios = BTreeSet::new();
+        for path in navigator.reachable_paths() {
+            if navigator.state_for_path(&path).is_some_and(|state| state.is_complete()) {
+                if let Some(scenario) = navigator.graph().story_at(&path).and_then(|story| story.scenario) {
+                    scenarios.insert(scenario.key().to_string());
+                }
+                paths.insert(path);
+            }
+        }
+
+        let tables = [
+            ("paths", &story_data.fm_ending.paths, &paths),
+            ("scenarios", &story_data.fm_ending.scenarios, &scenarios),
+        ];
+        for (table, entries, used) in tables {
+            let mut unused: Vec<&String> = entries.keys().filter(|key| !used.contains(*key)).collect();
+            unused.sort();
+            for key in unused {
+                report.push(Diagnostic::warning(DiagnosticKind::UnusedEnding(format!("FM_ENDING.{}.{}", table, key))));
+            }
+        }
+    }
+
+    fn check_paths(report: &mut ValidationReport, story_data: &StoryData) {
+        // Every reachable node must have its story, and nothing else may exist
+        let max_depth = story_data.path_depth();
+        let expected: BTreeSet<String> = Self::expected_paths(&Self::choice_levels(story_data))
+            .into_iter()
+            .collect();
+        for path in &expected {
+            if !story_data.fm_story.contains_key(path) {
+                report.push(Diagnostic::error(DiagnosticKind::MissingPath(path.clone())));
+            }
+        }
+        let mut extra: Vec<&String> = story_data
+            .fm_story
+            .keys()
+            .filter(|key| !expected.contains(*key))
+            .collect();
+        extra.sort();
+        for path in extra {
+            report.push(Diagnostic::warning(DiagnosticKind::ExtraPath(path.clone())));
+        }
+
+        // FM_CHOICE.n leads into level n; FM_CHOICE.0 is the optional intro
+        for level in 1..=max_depth {
+            if story_data.get_choice_by_level(level).is_none() {
+                report.push(Diagnostic::error(DiagnosticKind::MissingChoiceLevel(level)));
+            }
+        }
+        let mut unused: Vec<&String> = story_data
+            .fm_choice
+            .keys()
+            .filter(|key| !matches!(key.parse::<usize>(), Ok(level) if level <= max_depth))
+            .collect();
+        unused.sort();
+        for key in unused {
+            report.push(Diagnostic::warning(DiagnosticKind::UnusedChoiceLevel(key.clone())));
+        }
+
+        for level in 1..=max_depth {
+            if let Some(choice) = story_data.get_choice_by_level(level) {
+                Self::check_options(report, &format!("FM_CHOICE.{}", level), choice);
+            }
+        }
+    }
+
+    // Graph stories name their rooms; every option must lead to one of them
+    fn check_graph(report: &mut ValidationReport, story_data: &StoryData) {
+        let start = story_data.start_node();
+        if !story_data.fm_node.contains_key(start) {
+            report.push(Diagnostic::error(DiagnosticKind::MissingNode(start.to_string())));
+        }
+
+        let mut ids: Vec<&String> = story_data.fm_node.keys().collect();
+        ids.sort();
+        for id in &ids {
+            let Some(choice) = &story_data.fm_node[*id].choice else {
+                continue;
+            };
+            let key = format!("FM_NODE.{}.choice", id);
+            Self::check_options(report, &key, choice);
+            for option in &choice.options {
+                match &option.target {
+                    None => report.push(Diagnostic::error(DiagnosticKind::MissingTarget {
+                        key: key.clone(),
+                        option: option.key,
+                    })),
+                    Some(target) if !story_data.fm_node.contains_key(target) => {
+                        report.push(Diagnostic::error(DiagnosticKind::UnknownTarget {
+                            key: key.clone(),
+                            target: target.clone(),
+                        }))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let mut reached = BTreeSet::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            if !reached.insert(id) {
+                continue;
+            }
+            let targets = story_data.fm_node.get(id).and_then(|node| node.choice.as_ref());
+            for option in targets.into_iter().flat_map(|choice| &choice.options) {
+                if let Some(target) = option.target.as_deref() {
+                    queue.push_back(target);
+                }
+            }
+        }
+        for id in ids {
+            if !reached.contains(id.as_str()) {
+                report.push(Diagnostic::warning(DiagnosticKind::UnreachableNode(id.clone())));
+            }
+        }
+
+        // Only the intro is read from FM_CHOICE, and FM_STORY isn't read at all
+        let mut unused: Vec<&String> = story_data.fm_choice.keys().filter(|key| *key != "0").collect();
+        unused.sort();
+        for key in unused {
+            report.push(Diagnostic::warning(DiagnosticKind::UnusedChoiceLevel(key.clone())));
+        }
+        let mut extra: Vec<&String> = story_data.fm_story.keys().collect();
+        extra.sort();
+        for path in extra {
+            report.push(Diagnostic::warning(DiagnosticKind::ExtraPath(path.clone())));
+        }
+    }
+
+    pub fn validate_str(content: &str) -> (Option<StoryData>, ValidationReport) {
+        let mut report = ValidationReport::default();
+
+        let table: toml::Table = match toml::from_str(content) {
+            Ok(table) => table,
+            Err(e) => {
+                report.push(Diagnostic::error(DiagnosticKind::Syntax(e.to_string())));
+                return (None, report);
+            }
+        };
+
+        // Collect structural problems first so a single bad node doesn't hide the rest
+        // Graph stories replace the path-keyed sections with FM_NODE
+        let required = if table.contains_key("FM_NODE") {
+            [REQUIRED_SECTIONS.as_slice(), &["FM_NODE"]].concat()
+        } else {
+            [REQUIRED_SECTIONS.as_slice(), PATH_SECTIONS.as_slice()].concat()
+        };
+        for section in required {
+            match table.get(section) {
+                None => report.push(Diagnostic::error(DiagnosticKind::MissingSection(section.to_string()))),
+                Some(value) => Self::check_section(&mut report, section, value),
+            }
+        }
+        if let Some(endings) = table.get("FM_ENDING").and_then(toml::Value::as_table) {
+            for (name, value) in endings {
+                Self::check_section(&mut report, &format!("FM_ENDING.{}", name), value);
+            }
+        }
+        if !report.is_valid() {
+            return (None, report);
+        }
+
+        match StoryData::deserialize(toml::Value::Table(table)) {
+            Ok(story_data) => {
+                report.diagnostics.extend(Self::validate(&story_data).diagnostics);
+                (Some(story_data), report)
+            }
+            Err(e) => {
+                report.push(Diagnostic::error(DiagnosticKind::Syntax(e.to_string())));
+                (None, report)
+            }
+        }
+    }
+
+    fn check_section(report: &mut ValidationReport, section: &str, value: &toml::Value) {
+        match section {
+            "FM_CHOICE" | "FM_STORY" | "FM_NODE" | "FM_ENDING.paths" | "FM_ENDING.scenarios" => {
+                let Some(nodes) = value.as_table() else {
+                    report.push(Diagnostic::error(DiagnosticKind::MissingSection(section.to_string())));
+                    return;
+                };
+                for (key, node) in nodes {
+                    let key = format!("{}.{}", section, key);
+                    Self::check_fields(report, &key, node, &["title", "story"]);
+                    Self::check_scenario(report, &key, node);
+                    match section {
+                        "FM_CHOICE" => Self::check_choice_fields(report, &key, node, false),
+                        "FM_NODE" => {
+                            if let Some(choice) = node.get("choice") {
+                                let key = format!("{}.choice", key);
+                                Self::check_fields(report, &key, choice, &["title", "story"]);
+                                Self::check_choice_fields(report, &key, choice, true);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => Self::check_fields(report, section, value, &["title", "story"]),
+        }
+    }
+
+    fn check_scenario(report: &mut ValidationReport, key: &str, node: &toml::Value) {
+        let Some(scenario) = node.get("scenario") else {
+            return;
+        };
+        if scenario.as_str().and_then(Scenario::from_key).is_none() {
+            report.push(Diagnostic::error(DiagnosticKind::UnknownScenario {
+                key: key.to_string(),
+                scenario: scenario.as_str().map_or_else(|| scenario.to_string(), str::to_string),
+            }));
+        }
+    }
+
+    // Choices either list `options` or use the older red/blue pair;
+    // graph options also need the node they lead to
+    fn check_choice_fields(report: &mut ValidationReport, key: &str, choice: &toml::Value, graph: bool) {
+        let Some(options) = choice.get("options") else {
+            if !graph {
+                Self::check_fields(report, key, choice, &["red", "blue"]);
+                Self::check_effects(report, key, choice, "red_effects");
+                Self::check_effects(report, key, choice, "blue_effects");
+            } else {
+                report.push(Diagnostic::error(DiagnosticKind::InvalidField {
+                    key: key.to_string(),
+                    field: "options".to_string(),
+                }));
+            }
+            return;
+        };
+        let Some(options) = options.as_array() else {
+            report.push(Diagnostic::error(DiagnosticKind::InvalidField {
+                key: key.to_string(),
+                field: "options".to_string(),
+            }));
+            return;
+        };
+        let fields: &[&str] = if graph {
+            &["key", "label", "color", "target"]
+        } else {
+            &["key", "label", "color"]
+        };
+        for (i, option) in options.iter().enumerate() {
+            let key = format!("{}.options[{}]", key, i);
+            Self::check_fields(report, &key, option, fields);
+            Self::check_effects(report, &key, option, "effects");
+        }
+    }
+
+    // Effects are optional, but must map axis names to whole numbers
+    fn check_effects(report: &mut ValidationReport, key: &str, node: &toml::Value, field: &str) {
+        let Some(effects) = node.get(field) else {
+            return;
+        };
+        if !effects.as_table().is_some_and(|axes| axes.values().all(toml::Value::is_integer)) {
+            report.push(Diagnostic::error(DiagnosticKind::InvalidField {
+                key: key.to_string(),
+                field: field.to_string(),
+            }));
+        }
+    }
+
+    fn check_fields(report: &mut ValidationReport, key: &str, node: &toml::Value, fields: &[&str]) {
+        for field in fields {
+            if node.get(field).and_then(|v| v.as_str()).is_none() {
+                report.push(Diagnostic::error(DiagnosticKind::InvalidField {
+                    key: key.to_string(),
+                    field: field.to_string(),
+                }));
+            }
+        }
+    }
+
+    fn check_options(report: &mut ValidationReport, key: &str, choice: &ChoiceData) {
+        if choice.options.len() < 2 {
+            report.push(Diagnostic::error(DiagnosticKind::TooFewOptions(key.to_string())));
+        }
+        let mut seen = BTreeSet::new();
+        for option in &choice.options {
+            if option.choice_type().is_none() {
+                report.push(Diagnostic::error(DiagnosticKind::InvalidOption {
+                    key: key.to_string(),
+                    option: option.key,
+                }));
+            } else if !seen.insert(option.key) {
+                report.push(Diagnostic::error(DiagnosticKind::DuplicateOption {
+                    key: key.to_string(),
+                    option: option.key,
+                }));
+            }
+        }
+    }
+
+    // Line breaks and indentation in the TOML don't count toward a story's length
+    pub fn story_chars(story: &str) -> usize {
+        story.chars().filter(|c| !c.is_whitespace()).count()
+    }
+
+    fn check_length(report: &mut ValidationReport, key: &str, story: &str) {
+        let chars = Self::story_chars(story);
+        if chars < MIN_STORY_CHARS {
+            report.push(Diagnostic::warning(DiagnosticKind::StoryTooShort {
+                key: key.to_string(),
+                chars,
+            }));
+        }
+    }
+}
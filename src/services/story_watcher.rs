@@ -0,0 +1,76 @@
+use crate::models::Locale;
+use crate::services::{SharedStoryData, StoryLoader, StoryLoaderError};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub struct StoryWatcher;
+
+impl StoryWatcher {
+    // Only a story that passes validation replaces the running one
+    pub fn reload(path: &Path, story: &SharedStoryData) -> Result<(), StoryLoaderError> {
+        let (story_data, report) = StoryLoader::load_validated(path)?;
+        let translations = StoryLoader::load_translations(path)?;
+        for warning in report.warnings() {
+            tracing::warn!("{}", warning);
+        }
+        story.replace(story_data, translations);
+        Ok(())
+    }
+
+    // The returned watcher stops watching when dropped, so the caller keeps it alive
+    pub fn spawn(path: PathBuf, story: SharedStoryData) -> notify::Result<RecommendedWatcher> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })?;
+
+        // Editors often save by renaming a temp file over the original, so watch the directory
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                if !event.paths.iter().any(|changed| Self::is_story_file(&path, changed)) {
+                    continue;
+                }
+
+                // One save fires a burst of events; let it settle and reload once
+                tokio::time::sleep(DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+
+                match Self::reload(&path, &story) {
+                    Ok(()) => tracing::info!(
+                        "Reloaded story from {} (revision {})",
+                        path.display(),
+                        story.revision()
+                    ),
+                    Err(StoryLoaderError::Invalid(report)) => {
+                        for diagnostic in report.errors() {
+                            tracing::error!("{}", diagnostic);
+                        }
+                        tracing::warn!("{} failed validation; keeping the last good story", path.display());
+                    }
+                    Err(e) => tracing::error!("Failed to reload {}: {}; keeping the last good story", path.display(), e),
+                }
+            }
+        });
+
+        Ok(watcher)
+    }
+
+    pub fn is_story_file(story_path: &Path, changed: &Path) -> bool {
+        let name = changed.file_name();
+        name == story_path.file_name()
+            || Locale::ALL
+                .iter()
+                .any(|locale| name == StoryLoader::translation_path(story_path, *locale).file_name())
+    }
+}
@@ -0,0 +1,310 @@
+use crate::models::{join_code, ChoiceData, GameState, Locale, VotePhase, VoteRequest, VoteRoomView, VoteRound};
+use crate::services::{PathNavigator, SharedStoryData};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
+use futures::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::watch;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum VoteError {
+    #[error("Path `{0}` doesn't lead to a choice")]
+    NoChoice(String),
+    #[error("No vote is open")]
+    NotOpen,
+    #[error("`{0}` is not one of the options")]
+    InvalidOption(char),
+    #[error("No free join code after {0} tries")]
+    NoFreeCode(usize),
+}
+
+// Codes are drawn at random, so give up rather than spin when nearly all are taken
+const JOIN_CODE_ATTEMPTS: usize = 100;
+
+// Open rooms by join code; a room lives as long as its presenter stays connected
+#[derive(Clone, Default)]
+pub struct VotingRooms {
+    rooms: Arc<Mutex<HashMap<String, Arc<VoteRoom>>>>,
+}
+
+impl VotingRooms {
+    pub fn create(&self, locale: Locale, max_depth: usize) -> Result<Arc<VoteRoom>, VoteError> {
+        let mut rooms = self.rooms.lock().unwrap_or_else(|e| e.into_inner());
+        let code = std::iter::repeat_with(join_code)
+            .take(JOIN_CODE_ATTEMPTS)
+            .find(|code| !rooms.contains_key(code))
+            .ok_or(VoteError::NoFreeCode(JOIN_CODE_ATTEMPTS))?;
+        let room = Arc::new(VoteRoom::new(code.clone(), locale, max_depth));
+        rooms.insert(code, room.clone());
+        Ok(room)
+    }
+
+    pub fn get(&self, code: &str) -> Option<Arc<VoteRoom>> {
+        self.rooms.lock().unwrap_or_else(|e| e.into_inner()).get(code).cloned()
+    }
+
+    // Tells everyone still connected that the room is gone
+    pub fn remove(&self, code: &str) {
+        let room = self.rooms.lock().unwrap_or_else(|e| e.into_inner()).remove(code);
+        if let Some(room) = room {
+            room.end();
+        }
+    }
+}
+
+struct RoomState {
+    game_state: GameState,
+    // The choice last put to a vote, with its round
+    choice: Option<ChoiceData>,
+    round: Option<VoteRound>,
+    // Bumped by every opened vote, so a stale countdown can't close a newer one
+    round_id: u64,
+    phase: VotePhase,
+    voters: usize,
+    next_voter: u64,
+}
+
+// The shared game a room votes on; every change is published to all its screens
+pub struct VoteRoom {
+    pub code: String,
+    pub locale: Locale,
+    state: Mutex<RoomState>,
+    updates: watch::Sender<VoteRoomView>,
+}
+
+impl VoteRoom {
+    fn new(code: String, locale: Locale, max_depth: usize) -> Self {
+        let state = RoomState {
+            game_state: GameState::with_max_depth(max_depth),
+            choice: None,
+            round: None,
+            round_id: 0,
+            phase: VotePhase::Waiting,
+            voters: 0,
+            next_voter: 0,
+        };
+        let (updates, _) = watch::channel(Self::view_of(&code, &state));
+        Self {
+            code,
+            locale,
+            state: Mutex::new(state),
+            updates,
+        }
+    }
+
+    fn view_of(code: &str, state: &RoomState) -> VoteRoomView {
+        VoteRoomView {
+            code: code.to_string(),
+            path: state.game_state.get_path().to_string(),
+            choice: state.choice.clone(),
+            phase: state.phase.clone(),
+            tallies: state.round.as_ref().map(VoteRound::tallies).unwrap_or_default(),
+            voters: state.voters,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RoomState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn publish(&self, state: &RoomState) {
+        self.updates.send_replace(Self::view_of(&self.code, state));
+    }
+
+    pub fn view(&self) -> VoteRoomView {
+        self.updates.borrow().clone()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<VoteRoomView> {
+        self.updates.subscribe()
+    }
+
+    pub fn game_state(&self) -> GameState {
+        self.lock().game_state.clone()
+    }
+
+    // An audience member's ID for the votes they cast
+    pub fn join(&self) -> u64 {
+        let mut state = self.lock();
+        state.voters += 1;
+        state.next_voter += 1;
+        let voter = state.next_voter;
+        self.publish(&state);
+        voter
+    }
+
+    pub fn leave(&self) {
+        let mut state = self.lock();
+        state.voters = state.voters.saturating_sub(1);
+        self.publish(&state);
+    }
+
+    // The presenter may have clicked ahead, so the vote is on the choice at `path`;
+    // returns the round for the countdown to close
+    pub fn open(&self, navigator: &PathNavigator, path: &str, seconds: u32) -> Result<u64, VoteError> {
+        let game_state = navigator
+            .state_for_path(path)
+            .ok_or_else(|| VoteError::NoChoice(path.to_string()))?;
+        let choice = navigator
+            .get_current_choice(&game_state)
+            .cloned()
+            .ok_or_else(|| VoteError::NoChoice(path.to_string()))?;
+
+        let mut state = self.lock();
+        let round = VoteRound::new(&choice, seconds);
+        state.phase = VotePhase::Open { seconds_left: round.seconds_left };
+        state.round = Some(round);
+        state.round_id += 1;
+        state.game_state = game_state;
+        state.choice = Some(choice);
+        self.publish(&state);
+        Ok(state.round_id)
+    }
+
+    pub fn cast(&self, voter: u64, key: char) -> Result<(), VoteError> {
+        let mut state = self.lock();
+        if !matches!(state.phase, VotePhase::Open { .. }) {
+            return Err(VoteError::NotOpen);
+        }
+        let round = state.round.as_mut().ok_or(VoteError::NotOpen)?;
+        if !round.cast(voter, key) {
+            return Err(VoteError::InvalidOption(key));
+        }
+        self.publish(&state);
+        Ok(())
+    }
+
+    // One second of round `round_id`'s countdown; `false` once that round is over
+    pub fn tick(&self, navigator: &PathNavigator, round_id: u64) -> bool {
+        let mut state = self.lock();
+        if state.round_id != round_id || !matches!(state.phase, VotePhase::Open { .. }) {
+            return false;
+        }
+        let Some(round) = state.round.as_mut() else {
+            return false;
+        };
+        if round.tick() {
+            let seconds_left = round.seconds_left;
+            state.phase = VotePhase::Open { seconds_left };
+            self.publish(&state);
+            return true;
+        }
+        drop(state);
+        self.close(navigator);
+        false
+    }
+
+    // Ends the open vote and moves the shared game on by the majority choice;
+    // the choice and its tallies stay on screen until the next vote opens
+    pub fn close(&self, navigator: &PathNavigator) -> Option<char> {
+        let mut state = self.lock();
+        if !matches!(state.phase, VotePhase::Open { .. }) {
+            return None;
+        }
+        let winner = state.round.as_ref().and_then(VoteRound::majority);
+        if let Some(next) = winner.and_then(|key| navigator.state_for_path(&format!("{}{}", state.game_state.get_path(), key))) {
+            state.game_state = next;
+        }
+        state.phase = VotePhase::Decided { key: winner };
+        self.publish(&state);
+        winner
+    }
+
+    fn end(&self) {
+        let mut state = self.lock();
+        state.round = None;
+        state.phase = VotePhase::Ended;
+        self.publish(&state);
+    }
+}
+
+pub struct VoteSocket;
+
+impl VoteSocket {
+    // The presenter's socket creates the room and closes it when it disconnects
+    pub async fn host(mut socket: WebSocket, story: SharedStoryData, rooms: VotingRooms, locale: Locale) {
+        let max_depth = story.pack(locale).graph.max_depth;
+        let room = match rooms.create(locale, max_depth) {
+            Ok(room) => room,
+            Err(e) => {
+                tracing::warn!("Could not open a voting room: {}", e);
+                let frame = CloseFrame {
+                    code: close_code::AGAIN,
+                    reason: e.to_string().into(),
+                };
+                let _ = socket.send(Message::Close(Some(frame))).await;
+                return;
+            }
+        };
+        tracing::info!("Opened voting room {}", room.code);
+        Self::run(socket, story, room.clone(), None).await;
+        rooms.remove(&room.code);
+        tracing::info!("Closed voting room {}", room.code);
+    }
+
+    pub async fn audience(socket: WebSocket, story: SharedStoryData, room: Arc<VoteRoom>) {
+        let voter = room.join();
+        Self::run(socket, story, room.clone(), Some(voter)).await;
+        room.leave();
+    }
+
+    // Sends the room's view after every change and applies what comes back;
+    // only the audience votes and only the presenter opens and closes votes
+    async fn run(socket: WebSocket, story: SharedStoryData, room: Arc<VoteRoom>, voter: Option<u64>) {
+        let (mut sender, mut receiver) = socket.split();
+        let mut updates = room.subscribe();
+        let send_updates = tokio::spawn(async move {
+            loop {
+                let view = updates.borrow_and_update().clone();
+                let Ok(text) = serde_json::to_string(&view) else {
+                    break;
+                };
+                if sender.send(Message::Text(text)).await.is_err() || updates.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(Ok(message)) = receiver.next().await {
+            let request = match message {
+                Message::Text(text) => serde_json::from_str::<VoteRequest>(&text),
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(request) = request else {
+                continue;
+            };
+            let pack = story.pack(room.locale);
+            let result = match (request, voter) {
+                (VoteRequest::Vote { key }, Some(voter)) => room.cast(voter, key),
+                (VoteRequest::Open { path, seconds }, None) => room
+                    .open(&pack.navigator(), &path, seconds)
+                    .map(|round_id| Self::count_down(story.clone(), room.clone(), round_id)),
+                (VoteRequest::Close, None) => {
+                    room.close(&pack.navigator());
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                tracing::debug!("Voting room {}: {}", room.code, e);
+            }
+        }
+        send_updates.abort();
+    }
+
+    fn count_down(story: SharedStoryData, room: Arc<VoteRoom>, round_id: u64) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if !room.tick(&story.pack(room.locale).navigator(), round_id) {
+                    break;
+                }
+            }
+        });
+    }
+}
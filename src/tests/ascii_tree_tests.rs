@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::utils::*;
+
+    #[test]
+    fn test_side_by_side_aligns_the_right_panel() {
+        let panels = AsciiTreeGenerator::side_by_side("ab\nabcd\na", "1\n2", 2);
+        assert_eq!(panels, "ab    1\nabcd  2\na\n");
+    }
+
+    #[test]
+    fn test_side_by_side_counts_wide_characters() {
+        let panels = AsciiTreeGenerator::side_by_side("📚 开始\nab", "x\ny\nz", 1);
+        let lines: Vec<&str> = panels.lines().collect();
+        assert_eq!(lines, ["📚 开始 x", "ab      y", "        z"]);
+    }
+
+    #[test]
+    fn test_tree_and_statistics_panels() {
+        let game_state = GameState::from_path("RB", 3).unwrap();
+        let tree = AsciiTreeGenerator::generate_tree(&game_state, Locale::En);
+        let stats = AsciiTreeGenerator::generate_statistics(&game_state, Locale::En);
+        let panels = AsciiTreeGenerator::side_by_side(&tree, &stats, 4);
+
+        assert_eq!(panels.lines().count(), tree.lines().count().max(stats.lines().count()));
+        assert!(panels.lines().next().unwrap().ends_with("    Statistics:"));
+        assert!(panels.contains("Path: RB"));
+    }
+
+    fn binary_graph(max_depth: usize) -> StoryGraph {
+        let mut story = format!(
+            "[FM_META]\nmax_depth = {}\n\n[FM_START]\ntitle = \"Gate\"\nstory = \"\"\n\n[FM_NOEND]\ntitle = \"\"\nstory = \"\"\n",
+            max_depth
+        );
+        for level in 1..=max_depth {
+            story.push_str(&format!(
+                "\n[FM_CHOICE.{}]\ntitle = \"\"\nstory = \"\"\nred = \"Red\"\nblue = \"Blue\"\n",
+                level
+            ));
+        }
+        let mut paths = vec![String::new()];
+        for _ in 0..max_depth {
+            paths = paths.iter().flat_map(|path| [format!("{}R", path), format!("{}B", path)]).collect();
+            for path in &paths {
+                story.push_str(&format!("\n[FM_STORY.{}]\ntitle = \"Room {}\"\nstory = \"\"\n", path, path));
+            }
+        }
+        let story_data: StoryData = toml::from_str(&story).unwrap();
+        StoryGraph::from_story_data(&story_data)
+    }
+
+    #[test]
+    fn test_full_tree_folds_around_the_current_path() {
+        let graph = binary_graph(3);
+        let tree = AsciiTreeGenerator::generate_full_tree(&graph, "RB", TreeOptions::new(3));
+
+        assert_eq!(tree, "\
+📚 Gate
+├─ 🔴 R ●
+│  ├─ 🔴 RR …
+│  └─ 🔵 RB ◀
+│     ├─ 🔴 RBR
+│     └─ 🔵 RBB
+└─ 🔵 B …
+");
+    }
+
+    #[test]
+    fn test_full_tree_expanded_with_depth_limit() {
+        let graph = binary_graph(3);
+        let options = TreeOptions {
+            depth: 2,
+            collapse: false,
+            titles: true,
+        };
+        let tree = AsciiTreeGenerator::generate_full_tree(&graph, "", options);
+
+        assert!(tree.starts_with("📚 Gate ◀\n"));
+        assert_eq!(tree.lines().count(), 7);
+        assert!(tree.contains("│  └─ 🔵 RB Room RB …\n"));
+        assert!(tree.contains("   ├─ 🔴 BR Room BR …\n"));
+        assert!(!tree.contains("RRR"));
+    }
+
+    #[test]
+    fn test_open_full_tree_lists_every_room_and_gap() {
+        let story_data = crate::tests::fixtures::story(r#"
+[FM_META]
+max_depth = 3
+
+[FM_CHOICE.1]
+title = "超级智能存在吗?"
+story = ""
+red = "红色"
+blue = "蓝色"
+
+[FM_CHOICE.2]
+title = "合作吗?"
+story = ""
+red = "红色"
+blue = "蓝色"
+
+[FM_STORY.R]
+title = "智能时代"
+story = "机器醒来"
+
+[FM_STORY.B]
+title = "人类时代"
+story = "人类继续"
+
+[FM_STORY.RR]
+title = "共治"
+story = "人机共治"
+"#);
+        let graph = StoryGraph::from_story_data(&story_data);
+        let options = TreeOptions {
+            collapse: false,
+            titles: true,
+            ..TreeOptions::new(graph.max_depth)
+        };
+        let tree = AsciiTreeGenerator::generate_full_tree(&graph, "", options);
+
+        assert!(tree.starts_with("📚 未来之门 ◀\n"));
+        assert!(tree.contains("├─ 🔴 R 智能时代\n"));
+        assert!(tree.contains("│  ├─ 🔴 RR 共治\n"));
+        assert!(tree.contains("│  └─ 🔵 RB ✗ RB\n"));
+        assert!(tree.contains("└─ 🔵 B 人类时代\n"));
+    }
+
+    #[test]
+    fn test_full_tree_depth_zero_is_just_the_gate() {
+        let graph = binary_graph(2);
+        assert_eq!(AsciiTreeGenerator::generate_full_tree(&graph, "R", TreeOptions::new(0)), "📚 Gate …\n");
+    }
+
+    #[test]
+    fn test_navigator_tree_is_the_full_tree() {
+        let graph = binary_graph(3);
+        let navigator = PathNavigator::new(&graph);
+        let game_state = navigator.state_for_path("RBR").unwrap();
+        let node = navigator.get_node(&game_state);
+
+        assert!(node.tree.contains("│     ├─ 🔴 RBR ◀\n"));
+        assert!(node.tree.contains("│     └─ 🔵 RBB\n"));
+        assert_eq!(node.tree, navigator.generate_tree_visualization(&game_state));
+    }
+
+    #[test]
+    fn test_layout_follows_the_folded_tree() {
+        let graph = binary_graph(3);
+        let layout = TreeLayout::build(&graph, "RB", TreeOptions::new(3));
+        let node = |path: &str| layout.nodes.iter().find(|node| node.path == path).unwrap();
+
+        let paths: Vec<&str> = layout.nodes.iter().map(|node| node.path.as_str()).collect();
+        assert_eq!(paths, ["", "R", "RR", "RB", "RBR", "RBB", "B"]);
+        assert_eq!(layout.edges.len(), 6);
+        assert_eq!(layout.edges.iter().filter(|edge| edge.on_path).count(), 2);
+
+        assert_eq!(node("").state, NodeState::Visited);
+        assert_eq!(node("R").state, NodeState::Visited);
+        assert_eq!(node("RB").state, NodeState::Current);
+        assert_eq!(node("RR").state, NodeState::Unexplored);
+        assert!(node("RR").folded && node("B").folded);
+        assert!(!node("RBR").folded);
+    }
+
+    #[test]
+    fn test_layout_centers_parents_over_children() {
+        let graph = binary_graph(3);
+        let layout = TreeLayout::build(&graph, "RB", TreeOptions::new(3));
+        let node = |path: &str| layout.nodes.iter().find(|node| node.path == path).unwrap();
+
+        assert_eq!(node("RB").x, (node("RBR").x + node("RBB").x) / 2.0);
+        assert_eq!(node("").x, (node("R").x + node("B").x) / 2.0);
+        assert!(node("R").y < node("RB").y && node("RB").y < node("RBR").y);
+
+        let leaves = ["RR", "RBR", "RBB", "B"].map(|path| node(path).x);
+        assert!(leaves.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(layout.nodes.iter().all(|node| node.x > 0.0 && node.x < layout.width));
+        assert!(layout.nodes.iter().all(|node| node.y > 0.0 && node.y < layout.height));
+    }
+
+    #[test]
+    fn test_layout_exports_as_svg() {
+        let graph = binary_graph(2);
+        let layout = TreeLayout::build(&graph, "B", TreeOptions::new(2));
+        let svg = layout.to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(svg.matches("<line ").count(), layout.edges.len());
+        assert_eq!(svg.matches("<circle ").count(), layout.nodes.len());
+        assert!(svg.contains("<g class=\"tree-node current\">\n    <title>Room B</title>"));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::*;
+    use crate::tests::fixtures;
+
+    // Declares three levels but only has the first choice and three rooms
+    const PARTIAL_STORY: &str = r#"
+[FM_META]
+max_depth = 3
+
+[FM_CHOICE.1]
+title = "超级智能存在吗?"
+story = "说明"
+red = "红色"
+blue = "蓝色"
+
+[FM_CHOICE.2]
+title = "合作吗?"
+story = "说明"
+red = "红色"
+blue = "蓝色"
+
+[FM_STORY.R]
+title = "智能时代"
+story = "机器醒来"
+
+[FM_STORY.B]
+title = "人类时代"
+story = "人类继续"
+
+[FM_STORY.RR]
+title = "共治"
+story = "人机共治"
+"#;
+
+    #[test]
+    fn test_stats_group_rooms_by_level() {
+        let stats = StoryStats::collect(&fixtures::story(PARTIAL_STORY));
+
+        let keys: Vec<&str> = stats.rooms.iter().map(|room| room.key.as_str()).collect();
+        assert_eq!(keys, vec!["FM_START", "FM_STORY.B", "FM_STORY.R", "FM_STORY.RR"]);
+        assert_eq!(stats.rooms[3].chars, 4);
+
+        assert_eq!(stats.levels.len(), 3);
+        assert_eq!(stats.levels[1].rooms, 2);
+        assert_eq!(stats.levels[1].total_chars, 8);
+        assert_eq!(stats.levels[1].short_rooms, 2);
+        assert_eq!(stats.total_chars(), 2 + 8 + 4);
+    }
+
+    #[test]
+    fn test_stats_cover_the_full_story() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let stats = StoryStats::collect(&story_data);
+
+        assert_eq!(stats.rooms.len(), 127);
+        assert_eq!(stats.levels.iter().map(|level| level.rooms).collect::<Vec<_>>(), vec![1, 2, 4, 8, 16, 32, 64]);
+    }
+
+    #[test]
+    fn test_scaffold_fills_missing_branches() {
+        let story_data = fixtures::story(PARTIAL_STORY);
+
+        let sections = StoryScaffold::missing_sections(&story_data, "").unwrap();
+        assert!(sections.contains("[FM_CHOICE.3]"));
+        assert!(!sections.contains("[FM_CHOICE.2]"));
+        assert!(sections.contains("[FM_STORY.RRB]"));
+        assert!(sections.contains("[FM_STORY.RB]"));
+        assert!(sections.contains("[FM_STORY.BB]"));
+        assert!(!sections.contains("[FM_STORY.RR]"));
+
+        let below_b = StoryScaffold::missing_sections(&story_data, "B").unwrap();
+        assert!(below_b.contains("[FM_STORY.BR]"));
+        assert!(!below_b.contains("[FM_STORY.RB]"));
+        assert_eq!(StoryScaffold::missing_sections(&story_data, "BBBB"), None);
+
+        // Appending the scaffold leaves only content problems behind
+        let (scaffolded, report) = StoryValidator::validate_str(&fixtures::source(&format!("{}{}", PARTIAL_STORY, sections)));
+        assert!(scaffolded.is_some());
+        assert!(report.is_valid(), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn test_scaffold_graph_targets() {
+        let story = r#"
+[FM_NODE.start]
+title = "未来之门"
+story = "门前"
+
+[FM_NODE.start.choice]
+title = "超级智能存在吗?"
+story = "说明"
+options = [
+    { key = "R", label = "红色", color = "red", target = "ai" },
+    { key = "B", label = "蓝色", color = "blue", target = "human era" },
+]
+"#;
+        let story_data = fixtures::story(story);
+        let sections = StoryScaffold::missing_sections(&story_data, "").unwrap();
+        assert_eq!(sections.matches("\n[").count(), 2);
+        assert!(sections.contains("[FM_NODE.ai]"));
+        assert!(sections.contains("[FM_NODE.\"human era\"]"));
+
+        let (_, report) = StoryValidator::validate_str(&fixtures::source(&format!("{}{}", story, sections)));
+        assert!(report.is_valid(), "{:?}", report.diagnostics);
+    }
+}
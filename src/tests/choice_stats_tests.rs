@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use std::collections::HashMap;
+
+    fn stats(red: u64, blue: u64) -> ChoiceStats {
+        let story_data = crate::services::StoryLoader::load_default().unwrap();
+        let choice = story_data.get_choice_by_level(0).cloned().unwrap();
+        ChoiceStats::new("", &choice, &HashMap::from([('R', red), ('B', blue), ('X', 9)]))
+    }
+
+    #[test]
+    fn test_shares_follow_the_options() {
+        let stats = stats(5, 3);
+        assert_eq!(stats.shares.iter().map(|share| (share.key, share.count)).collect::<Vec<_>>(), [('R', 5), ('B', 3)]);
+        assert_eq!(stats.total(), 8);
+        assert!(stats.share('X').is_none());
+    }
+
+    #[test]
+    fn test_percent_rounds_to_nearest() {
+        assert_eq!(stats(5, 3).percent('R'), Some(63));
+        assert_eq!(stats(5, 3).percent('B'), Some(38));
+        assert_eq!(stats(5, 3).percent('X'), None);
+        assert_eq!(stats(0, 0).percent('R'), None);
+        assert_eq!(stats(1, 0).percent('B'), Some(0));
+    }
+
+    #[test]
+    fn test_step_to_only_matches_the_next_node() {
+        let stats = ChoiceStats { path: "R".to_string(), ..stats(1, 1) };
+        assert_eq!(stats.step_to("RB"), Some('B'));
+        assert_eq!(stats.step_to("R"), None);
+        assert_eq!(stats.step_to("RBR"), None);
+        assert_eq!(stats.step_to("BB"), None);
+        assert_eq!(stats.step_to("RX"), None);
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod log_tests {
+    use crate::models::StoryGraph;
+    use crate::services::*;
+
+    fn graph() -> StoryGraph {
+        StoryGraph::from_story_data(&StoryLoader::load_default().unwrap())
+    }
+
+    #[test]
+    fn test_log_counts_choices_per_node() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let log = ChoiceLog::in_memory().unwrap();
+        log.record(&navigator, "", 'R').unwrap();
+        log.record(&navigator, "", 'R').unwrap();
+        log.record(&navigator, "", 'B').unwrap();
+        log.record(&navigator, "R", 'B').unwrap();
+
+        let stats = log.stats(&navigator, "").unwrap().unwrap();
+        assert_eq!(stats.total(), 3);
+        assert_eq!(stats.percent('R'), Some(67));
+        assert_eq!(log.stats(&navigator, "R").unwrap().unwrap().percent('B'), Some(100));
+        assert_eq!(log.stats(&navigator, "B").unwrap().unwrap().total(), 0);
+    }
+
+    #[test]
+    fn test_log_rejects_choices_the_story_doesnt_offer() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let log = ChoiceLog::in_memory().unwrap();
+
+        assert!(matches!(log.record(&navigator, "", 'X'), Err(ChoiceLogError::NotOffered { .. })));
+        assert!(matches!(log.record(&navigator, "Q", 'R'), Err(ChoiceLogError::NotOffered { .. })));
+        let ending = "R".repeat(navigator.graph().max_depth);
+        assert!(log.record(&navigator, &ending, 'R').is_err());
+        assert!(log.stats(&navigator, &ending).unwrap().is_none());
+        assert!(log.counts("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_counts_survive_reopening() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let path = std::env::temp_dir().join(format!("l3_choices_{}.sqlite3", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        ChoiceLog::open(&path).unwrap().record(&navigator, "", 'B').unwrap();
+        let reopened = ChoiceLog::open(&path).unwrap();
+        assert_eq!(reopened.counts("").unwrap().get(&'B'), Some(&1));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_record_choice_needs_a_choice_log() {
+        use crate::models::Locale;
+        use leptos::{create_runtime, provide_context, ServerFnError};
+
+        let runtime = create_runtime();
+        provide_context(SharedStoryData::new(StoryLoader::load_default().unwrap(), Default::default()));
+        let missing = record_choice(String::new(), 'R', Locale::default()).await;
+        provide_context(ChoiceLog::in_memory().unwrap());
+        let recorded = record_choice(String::new(), 'R', Locale::default()).await;
+        runtime.dispose();
+
+        assert_eq!(
+            missing.unwrap_err(),
+            ServerFnError::new("Choice statistics are unavailable: no choice log was provided")
+        );
+        assert_eq!(recorded.unwrap().unwrap().total(), 1);
+    }
+}
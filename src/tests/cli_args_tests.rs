@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_option_removes_name_and_value() {
+        let mut list = args(&["--file", "story.toml", "stats"]);
+        assert_eq!(take_option(&mut list, "--file"), Ok(Some("story.toml".to_string())));
+        assert_eq!(take_option(&mut list, "--lang"), Ok(None));
+        assert_eq!(list, args(&["stats"]));
+
+        let mut list = args(&["stats", "--depth"]);
+        assert_eq!(take_option(&mut list, "--depth"), Err("--depth needs a value".to_string()));
+    }
+
+    #[test]
+    fn test_take_flag_removes_every_copy() {
+        let mut list = args(&["--all", "paths", "--all"]);
+        assert!(take_flag(&mut list, "--all"));
+        assert!(!take_flag(&mut list, "--titles"));
+        assert_eq!(list, args(&["paths"]));
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::tests::fixtures;
+
+    const ENDINGS: &str = r#"
+[FM_ENDING.paths.RR]
+title = "守门人的结局"
+story = "只有这一条路通向这里"
+
+[FM_ENDING.scenarios.gatekeeper]
+title = "守门人们"
+story = "被路径专属的结局盖过"
+
+[FM_ENDING.scenarios.reversion]
+title = "回到过去"
+story = "所有回归的结局共用这一段"
+"#;
+
+    fn ending_source(extra: &str) -> String {
+        fixtures::source(&format!("{}{}{}", fixtures::TWO_LEVEL, ENDINGS, extra))
+    }
+
+    fn ending_story() -> StoryData {
+        StoryValidator::validate_str(&ending_source("")).0.unwrap()
+    }
+
+    #[test]
+    fn test_ending_falls_back_from_path_to_scenario_to_noend() {
+        let graph = StoryGraph::from_story_data(&ending_story());
+
+        assert_eq!(graph.ending_key("RR").as_deref(), Some("paths.RR"));
+        assert_eq!(graph.ending_at("RR").title, "守门人的结局");
+        assert_eq!(graph.ending_key("RB").as_deref(), Some("scenarios.reversion"));
+        assert_eq!(graph.ending_at("BR").title, "回到过去");
+        assert_eq!(graph.ending_key("BB"), None);
+        assert_eq!(graph.ending_at("BB").title, "未完待续");
+    }
+
+    #[test]
+    fn test_finished_run_shows_its_own_ending() {
+        let graph = StoryGraph::from_story_data(&ending_story());
+        let navigator = PathNavigator::new(&graph);
+
+        let node = navigator.get_node(&navigator.state_for_path("RB").unwrap());
+        assert_eq!(node.final_story.unwrap().title, "回到过去");
+        let node = navigator.get_node(&navigator.state_for_path("BB").unwrap());
+        assert_eq!(node.final_story.unwrap().title, "未完待续");
+        let node = navigator.get_node(&navigator.state_for_path("R").unwrap());
+        assert!(node.final_story.is_none());
+
+        let history = HistoryBuilder::build(navigator.graph(), &navigator.state_for_path("RR").unwrap());
+        assert_eq!(history.epilogue.title, "守门人的结局");
+    }
+
+    #[test]
+    fn test_validator_checks_ending_entries() {
+        let (story_data, report) = StoryValidator::validate_str(&ending_source(""));
+        assert!(story_data.is_some());
+        assert!(!report.diagnostics.iter().any(|d| matches!(d.kind, DiagnosticKind::UnusedEnding(_))));
+
+        let content = ending_source("\n[FM_ENDING.paths.RRR]\ntitle = \"太深\"\nstory = \"\"\n\n[FM_ENDING.scenarios.zookeeper]\ntitle = \"t\"\n");
+        let (_, report) = StoryValidator::validate_str(&content);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+        assert!(kinds.contains(&&DiagnosticKind::InvalidField {
+            key: "FM_ENDING.scenarios.zookeeper".to_string(),
+            field: "story".to_string(),
+        }));
+
+        let content = ending_source("\n[FM_ENDING.paths.RRR]\ntitle = \"太深\"\nstory = \"\"\n\n[FM_ENDING.scenarios.zookeeper]\ntitle = \"t\"\nstory = \"s\"\n");
+        let (_, report) = StoryValidator::validate_str(&content);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+        assert!(kinds.contains(&&DiagnosticKind::UnusedEnding("FM_ENDING.paths.RRR".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::UnusedEnding("FM_ENDING.scenarios.zookeeper".to_string())));
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_ending_keys_dont_mix_paths_and_scenarios() {
+        let (_, report) = StoryValidator::validate_str(&ending_source("\n[FM_ENDING.paths.reversion]\ntitle = \"t\"\nstory = \"s\"\n"));
+        assert!(report.diagnostics.iter().any(|d| d.kind == DiagnosticKind::UnusedEnding("FM_ENDING.paths.reversion".to_string())));
+
+        // Entries written straight under FM_ENDING are no longer read
+        let (story_data, _) = StoryValidator::validate_str(&ending_source("\n[FM_ENDING.BB]\ntitle = \"t\"\nstory = \"s\"\n"));
+        assert!(story_data.is_none());
+    }
+
+    #[test]
+    fn test_translated_endings_fall_back_to_default() {
+        let story_data = ending_story();
+        let translation: StoryTranslation = toml::from_str(
+            "[FM_ENDING.scenarios.reversion]\ntitle = \"Back to the past\"\nstory = \"Every reversion ending shares this\"\n",
+        )
+        .unwrap();
+        let english = story_data.localized(&translation);
+
+        assert_eq!(english.fm_ending.scenarios["reversion"].title, "Back to the past");
+        assert_eq!(english.fm_ending.paths["RR"].title, "守门人的结局");
+        assert_eq!(english.fm_ending.scenarios.len(), story_data.fm_ending.scenarios.len());
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::tests::fixtures;
+
+    const SMALL_STORY: &str = r#"
+[FM_CHOICE.1]
+title = "超级智能存在吗?"
+story = "说明"
+red = "红色: \"存在\""
+blue = "蓝色: 不存在"
+
+[FM_STORY.R]
+title = "智能时代"
+story = "机器醒来"
+gem = { name = "时间宝石", year = 2045, description = "第一颗宝石" }
+
+[FM_STORY.B]
+title = "人类时代"
+story = "人类继续"
+"#;
+
+    fn small_story() -> StoryData {
+        fixtures::story(SMALL_STORY)
+    }
+
+    #[test]
+    fn test_dot_export_labels_nodes_and_edges() {
+        let dot = StoryExporter::to_dot(&small_story(), NodeColoring::Plain);
+
+        assert!(dot.starts_with("digraph story {\n"));
+        assert!(dot.contains("    \"FM_START\" [label=\"FM_START\\n未来之门\"];\n"));
+        assert!(dot.contains("    \"FM_STORY.R\" [label=\"R\\n智能时代\"];\n"));
+        assert!(dot.contains("    \"FM_START\" -> \"FM_STORY.R\" [label=\"红色: \\\"存在\\\"\", color=\"red\"];\n"));
+        assert!(dot.contains("    \"FM_START\" -> \"FM_STORY.B\" [label=\"蓝色: 不存在\", color=\"blue\"];\n"));
+        assert!(!dot.contains("#d4edda") && !dot.contains("#fde2c8"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_mermaid_export_numbers_nodes() {
+        let mermaid = StoryExporter::to_mermaid(&small_story(), NodeColoring::Plain);
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    n0[\"FM_START<br/>未来之门\"]\n"));
+        assert!(mermaid.contains("    n0 -->|\"红色: #quot;存在#quot;\"| n2\n"));
+        assert!(mermaid.contains("    linkStyle 1 stroke:blue\n"));
+        assert!(!mermaid.contains("classDef"));
+    }
+
+    #[test]
+    fn test_mermaid_export_escapes_markup() {
+        let mut story_data = small_story();
+        story_data.fm_story.get_mut("R").unwrap().title = "<b>#1</b> & \"AI\"".to_string();
+        let mermaid = StoryExporter::to_mermaid(&story_data, NodeColoring::Plain);
+
+        assert!(mermaid.contains("    n2[\"R<br/>#lt;b#gt;#35;1#lt;/b#gt; & #quot;AI#quot;\"]\n"));
+    }
+
+    #[test]
+    fn test_export_colors_by_validation() {
+        let dot = StoryExporter::to_dot(&small_story(), NodeColoring::Validation);
+        // Every room is too short, and B has no gem; neither is an error
+        assert!(dot.contains("    \"FM_STORY.B\" [label=\"B\\n人类时代\", fillcolor=\"#fde2c8\"];\n"));
+
+        let mut broken = small_story();
+        broken.fm_story.remove("B");
+        let dot = StoryExporter::to_dot(&broken, NodeColoring::Validation);
+        assert!(dot.contains("    \"FM_STORY.B\" [label=\"✗ B\", fillcolor=\"#f5a3a3\", style=\"rounded,filled,dashed\"];\n"));
+
+        let mermaid = StoryExporter::to_mermaid(&broken, NodeColoring::Validation);
+        assert!(mermaid.contains("    classDef error fill:#f5a3a3\n"));
+        assert!(mermaid.contains("    style n2 stroke-dasharray: 5 5\n"));
+    }
+
+    #[test]
+    fn test_export_colors_by_length() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let mermaid = StoryExporter::to_mermaid(&story_data, NodeColoring::Length);
+
+        assert_eq!(mermaid.matches("\"]\n").count(), 127);
+        assert_eq!(mermaid.matches(" -->|").count(), 126);
+        assert!(mermaid.contains("classDef short"));
+        assert!(!mermaid.contains("classDef empty"));
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_tables() {
+        let report = StoryValidator::validate(&small_story());
+        let keys: Vec<String> = report.diagnostics.iter().filter_map(|d| d.key()).collect();
+        assert!(keys.contains(&"FM_STORY.B".to_string()));
+        assert!(keys.iter().all(|key| key.starts_with("FM_")));
+    }
+}
@@ -13,6 +13,20 @@ mod tests {
         assert!(!game_state.is_complete());
     }
     
+    #[test]
+    fn test_early_ending_keeps_story_depth() {
+        let mut game_state = GameState::new();
+        game_state.add_choice(ChoiceType::Red);
+        game_state.finish();
+        assert!(game_state.is_complete());
+        assert!(!game_state.can_make_choice());
+        assert_eq!(game_state.get_max_depth(), 1);
+
+        game_state.reset();
+        assert!(game_state.can_make_choice());
+        assert_eq!(game_state.get_max_depth(), DEFAULT_MAX_DEPTH);
+    }
+    
     #[test]
     fn test_game_state_choices() {
         let mut game_state = GameState::new();
@@ -76,4 +90,134 @@ mod tests {
         assert_eq!(game_state.choices.len(), 6);
         assert!(game_state.is_complete());
     }
+    
+    #[test]
+    fn test_save_slot_roundtrip() {
+        let mut game_state = GameState::new();
+        game_state.add_choice(ChoiceType::Red);
+        game_state.add_choice(ChoiceType::Blue);
+        
+        let slot = SaveSlot::new("第一次冒险", 1_700_000_000_000.0, game_state);
+        let json = serde_json::to_string(&slot).unwrap();
+        let restored: SaveSlot = serde_json::from_str(&json).unwrap();
+        
+        assert!(restored.is_compatible());
+        assert_eq!(restored.name, "第一次冒险");
+        assert_eq!(restored.state.get_path(), "RB");
+        assert_eq!(restored.state.get_level(), 2);
+        assert_eq!(restored.state.choices[1].choice_type, ChoiceType::Blue);
+        assert_eq!(restored.path_preview(Locale::ZhCn), "R → B");
+    }
+    
+    #[test]
+    fn test_save_slot_version_check() {
+        let mut slot = SaveSlot::new("旧存档", 0.0, GameState::new());
+        assert_eq!(slot.path_preview(Locale::ZhCn), "未来之门");
+        assert_eq!(slot.path_preview(Locale::En), "Gate of the Future");
+        
+        slot.version = SAVE_FORMAT_VERSION + 1;
+        assert!(!slot.is_compatible());
+    }
+
+    #[test]
+    fn test_unreadable_slots_stay_listed() {
+        let stored: Vec<serde_json::Value> = serde_json::from_str(r#"[
+            {"version":1,"name":"旧存档","saved_at":1.0,"state":{"choice_path":"R","current_level":1,"choices":[{"choice_type":"Red","level":0}]}},
+            {"version":1,"name":"坏存档","saved_at":2.0,"state":{"choice_path":7}},
+            {"version":2,"name":"新存档","saved_at":3.0,"state":{"choice_path":"","current_level":0,"choices":[]}}
+        ]"#).unwrap();
+        let slots: Vec<SaveSlot> = stored.iter().map(SaveSlot::from_value).collect();
+
+        // A v1 save from before gems, depths and world state still loads
+        assert!(slots[0].is_compatible());
+        assert_eq!(slots[0].state.get_path(), "R");
+        assert_eq!(slots[0].state.get_max_depth(), DEFAULT_MAX_DEPTH);
+
+        assert_eq!(slots[1].name, "坏存档");
+        assert_eq!(slots[1].saved_at, 2.0);
+        assert!(!slots[1].is_compatible());
+        assert_eq!(slots[1].path_preview(Locale::En), "Saved by a different version");
+        assert!(!slots[2].is_compatible());
+        assert_eq!(SaveSlot::stored_name(&stored[1]), Some("坏存档"));
+    }
+
+    #[test]
+    fn test_corrupt_slot_list_is_an_error_not_empty() {
+        assert!(SaveSlot::parse_stored(None).unwrap().is_empty());
+        assert_eq!(SaveSlot::parse_stored(Some(r#"[{"name":"旧存档"}]"#)).unwrap().len(), 1);
+
+        // A truncated write, or a map of slots from some other version
+        assert!(SaveSlot::parse_stored(Some(r#"[{"name":"旧存"#)).is_err());
+        assert!(SaveSlot::parse_stored(Some(r#"{"旧存档":{"choice_path":"R"}}"#)).is_err());
+    }
+    
+    fn test_gem(name: &str, year: i32) -> TimeGem {
+        TimeGem {
+            name: name.to_string(),
+            year,
+            description: "未来社会描述".to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_gem_collection() {
+        let mut game_state = GameState::new();
+        
+        // The starting gate holds no gem
+        assert!(!game_state.collect_gem(test_gem("起点", 2042)));
+        
+        game_state.add_choice(ChoiceType::Red);
+        assert!(game_state.collect_gem(test_gem("第一颗", 2165)));
+        assert!(!game_state.collect_gem(test_gem("第一颗", 2165)));
+        assert!(game_state.has_gem("R"));
+        
+        game_state.add_choice(ChoiceType::Blue);
+        assert!(!game_state.has_gem("RB"));
+        assert!(game_state.collect_gem(test_gem("第二颗", 3000)));
+        
+        let names: Vec<&str> = game_state.gems.iter().map(|c| c.gem.name.as_str()).collect();
+        assert_eq!(names, vec!["第一颗", "第二颗"]);
+        
+        game_state.reset();
+        assert!(game_state.gems.is_empty());
+    }
+    
+    #[test]
+    fn test_restore_gems_along_path() {
+        let mut collected = GameState::from_path("R", DEFAULT_MAX_DEPTH).unwrap();
+        collected.collect_gem(test_gem("红", 2165));
+        collected.add_choice(ChoiceType::Red);
+        collected.collect_gem(test_gem("红红", 3000));
+        
+        let mut game_state = GameState::from_path("RB", DEFAULT_MAX_DEPTH).unwrap();
+        game_state.restore_gems(&collected.gems);
+        
+        assert_eq!(game_state.gems.len(), 1);
+        assert_eq!(game_state.gems[0].path, "R");
+    }
+    
+    #[test]
+    fn test_loop_memory() {
+        let mut memory = LoopMemory::default();
+        
+        let mut unfinished = GameState::from_path("RB", DEFAULT_MAX_DEPTH).unwrap();
+        assert!(!memory.record_run(&unfinished, 0.0));
+        
+        unfinished.collect_gem(test_gem("红蓝", 3000));
+        let mut finished = GameState::from_path("RB", DEFAULT_MAX_DEPTH).unwrap();
+        finished.restore_gems(&unfinished.gems);
+        for _ in 0..4 {
+            finished.add_choice(ChoiceType::Red);
+        }
+        assert!(memory.record_run(&finished, 1.0));
+        assert!(memory.record_run(&finished, 2.0));
+        
+        assert_eq!(memory.loop_count(), 2);
+        assert!(memory.is_explored("RBR"));
+        assert!(!memory.is_explored("B"));
+        assert_eq!(memory.explored_choices(""), vec![ChoiceType::Red]);
+        assert_eq!(memory.explored_choices("RB"), vec![ChoiceType::Red]);
+        assert!(memory.explored_choices("B").is_empty());
+        assert_eq!(memory.remembered_gems().len(), 1);
+    }
 }
\ No newline at end of file
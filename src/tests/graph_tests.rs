@@ -0,0 +1,278 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::tests::fixtures;
+
+    // Both first choices merge in the same room, and the human branch can loop back to the gate
+    const GRAPH_STORY: &str = r#"
+[FM_META]
+max_depth = 4
+
+[FM_CHOICE.0]
+title = "选择未来"
+story = "说明"
+red = "红色"
+blue = "蓝色"
+
+[FM_NODE.start]
+title = "未来之门"
+story = "门前"
+
+[FM_NODE.start.choice]
+title = "超级智能存在吗?"
+story = "说明"
+options = [
+    { key = "R", label = "红色: SI 存在", color = "red", target = "ai" },
+    { key = "B", label = "蓝色: SI 没能诞生", color = "blue", target = "human" },
+]
+
+[FM_NODE.ai]
+title = "智能时代"
+story = "机器醒来"
+gem = { name = "时间宝石·智能时代", year = 2045, description = "第一颗宝石" }
+
+[FM_NODE.ai.choice]
+title = "合作吗?"
+story = "说明"
+options = [
+    { key = "R", label = "红色: 合作", color = "red", target = "merge" },
+    { key = "B", label = "蓝色: 对抗", color = "blue", target = "merge" },
+]
+
+[FM_NODE.human]
+title = "人类时代"
+story = "人类继续"
+
+[FM_NODE.human.choice]
+title = "继续前进吗?"
+story = "说明"
+options = [
+    { key = "R", label = "红色: 前进", color = "red", target = "merge" },
+    { key = "B", label = "蓝色: 回到原点", color = "blue", target = "start" },
+]
+
+[FM_NODE.merge]
+title = "殊途同归"
+story = "所有道路在此交汇"
+gem = { name = "时间宝石·殊途同归", year = 2100, description = "第二颗宝石" }
+"#;
+
+    fn graph_story() -> StoryData {
+        let (story_data, report) = StoryValidator::validate_str(&fixtures::source(GRAPH_STORY));
+        assert!(report.is_valid(), "{:?}", report.errors().collect::<Vec<_>>());
+        story_data.unwrap()
+    }
+
+    #[test]
+    fn test_path_story_loads_as_graph() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+
+        assert_eq!(graph.nodes.len(), story_data.fm_story.len() + 1);
+        assert_eq!(graph.max_depth, story_data.path_depth());
+        assert_eq!(graph.ending_count(), 64);
+        assert_eq!(graph.walk("RB").unwrap(), vec!["", "R", "RB"]);
+        assert_eq!(graph.story_at("RB").unwrap().title, story_data.fm_story["RB"].title);
+    }
+
+    #[test]
+    fn test_branches_merge() {
+        let story_data = graph_story();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+
+        for path in ["RR", "RB", "BR"] {
+            let state = navigator.state_for_path(path).unwrap();
+            assert_eq!(navigator.node_id(&state), Some("merge"));
+            assert!(state.is_complete());
+
+            let node = navigator.get_node(&state);
+            assert_eq!(node.max_depth, 4);
+            assert_eq!(node.game_state().unwrap().get_max_depth(), 2);
+            assert_eq!(node.story.unwrap().title, "殊途同归");
+            assert!(node.choice.is_none());
+            assert_eq!(node.final_story.unwrap().title, "未完待续");
+        }
+    }
+
+    #[test]
+    fn test_loop_back_to_start() {
+        let story_data = graph_story();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+
+        let looped = navigator.state_for_path("BB").unwrap();
+        assert_eq!(navigator.node_id(&looped), Some("start"));
+        assert!(!looped.is_complete());
+        assert_eq!(navigator.get_node(&looped).choice.unwrap().title, "超级智能存在吗?");
+
+        // The declared depth stops a run that keeps looping
+        let exhausted = navigator.state_for_path("BBBB").unwrap();
+        assert!(exhausted.is_complete());
+        assert!(navigator.state_for_path("BBBBR").is_none());
+        assert!(navigator.state_for_path("G").is_none());
+
+        assert_eq!(navigator.graph().ending_count(), 7);
+    }
+
+    #[test]
+    fn test_graph_measures_graph_stories() {
+        let graph = StoryGraph::from_story_data(&graph_story());
+        assert_eq!(graph.max_depth, 4);
+        assert_eq!(graph.ending_count(), 7);
+
+        // Without a declared depth the longest loop-free run counts
+        let (story_data, report) = StoryValidator::validate_str(&fixtures::source(&GRAPH_STORY.replace("[FM_META]\nmax_depth = 4\n", "")));
+        assert!(report.is_valid(), "{:?}", report.errors().collect::<Vec<_>>());
+        let graph = StoryGraph::from_story_data(&story_data.unwrap());
+        assert_eq!(graph.max_depth, 2);
+        assert_eq!(graph.ending_count(), 4);
+    }
+
+    #[test]
+    fn test_history_follows_graph() {
+        let story_data = graph_story();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        let mut state = navigator.state_for_path("RB").unwrap();
+        state.restore_gems(&[CollectedGem {
+            path: "RB".to_string(),
+            gem: navigator.graph().story_at("RB").unwrap().gem.clone().unwrap(),
+        }]);
+
+        let history = HistoryBuilder::build(navigator.graph(), &state);
+        let titles: Vec<&str> = history.entries.iter().map(|entry| entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["智能时代", "殊途同归"]);
+        assert_eq!(history.gem_count(), 1);
+        assert_eq!(history.prologue.title, "未来之门");
+    }
+
+    #[test]
+    fn test_graph_validation() {
+        let mut story_data = graph_story();
+        let orphan = story_data.fm_node["merge"].clone();
+        story_data.fm_node.insert("orphan".to_string(), orphan);
+        let human = story_data.fm_node.get_mut("human").unwrap();
+        human.choice.as_mut().unwrap().options[0].target = Some("nowhere".to_string());
+
+        let report = StoryValidator::validate(&story_data);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+
+        assert!(kinds.contains(&&DiagnosticKind::UnknownTarget {
+            key: "FM_NODE.human.choice".to_string(),
+            target: "nowhere".to_string(),
+        }));
+        assert!(kinds.contains(&&DiagnosticKind::UnreachableNode("orphan".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::MissingGem("FM_NODE.human".to_string())));
+
+        story_data.fm_meta.start = Some("gate".to_string());
+        let report = StoryValidator::validate(&story_data);
+        assert!(report.diagnostics.iter().any(|d| d.kind == DiagnosticKind::MissingNode("gate".to_string())));
+    }
+
+    #[test]
+    fn test_reachable_paths_cover_every_run() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let paths = PathNavigator::new(&StoryGraph::from_story_data(&story_data)).reachable_paths();
+        assert_eq!(paths.len(), 127);
+        assert_eq!(&paths[..3], &["", "R", "RR"]);
+
+        // The loop back to the gate is followed until the depth runs out
+        let graph = StoryGraph::from_story_data(&graph_story());
+        let navigator = PathNavigator::new(&graph);
+        let paths = navigator.reachable_paths();
+        let endings = paths
+            .iter()
+            .filter(|path| navigator.state_for_path(path).is_some_and(|state| state.is_complete()))
+            .count();
+        assert!(paths.contains(&"BBRR".to_string()));
+        assert_eq!(endings, navigator.graph().ending_count());
+    }
+
+    #[test]
+    fn test_depth_of_a_wide_merging_graph() {
+        // Thirty levels of three rooms, each leading to all three on the next level:
+        // 3^30 runs, so the depth has to come from each room once
+        const LEVELS: usize = 30;
+        let room = |level: usize, i: usize| if level == 0 { "start".to_string() } else { format!("n{}_{}", level, i) };
+        let mut story = String::new();
+        for level in 0..=LEVELS {
+            for i in 0..if level == 0 { 1 } else { 3 } {
+                story.push_str(&format!("\n[FM_NODE.{}]\ntitle = \"\"\nstory = \"\"\n", room(level, i)));
+                if level == LEVELS {
+                    continue;
+                }
+                let options: Vec<String> = ['R', 'B', 'G']
+                    .iter()
+                    .enumerate()
+                    .map(|(j, key)| format!("{{ key = \"{}\", label = \"\", color = \"\", target = \"{}\" }}", key, room(level + 1, j)))
+                    .collect();
+                story.push_str(&format!(
+                    "\n[FM_NODE.{}.choice]\ntitle = \"\"\nstory = \"\"\noptions = [{}]\n",
+                    room(level, i),
+                    options.join(", ")
+                ));
+            }
+        }
+        let story_data: StoryData = toml::from_str(&fixtures::source(&story)).unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+
+        assert_eq!(graph.max_depth, LEVELS);
+        assert_eq!(graph.ending_count(), 3usize.pow(LEVELS as u32));
+    }
+
+    #[test]
+    fn test_depth_of_a_looping_graph_counts_every_path() {
+        // `a` and `b` lead to each other, so `a` reached through `b` is shorter than
+        // `a` reached first; the longest run is B → a → b → c
+        let story = r#"
+[FM_NODE.start]
+title = ""
+story = ""
+
+[FM_NODE.start.choice]
+title = ""
+story = ""
+options = [
+    { key = "R", label = "", color = "red", target = "b" },
+    { key = "B", label = "", color = "blue", target = "a" },
+]
+
+[FM_NODE.b]
+title = ""
+story = ""
+
+[FM_NODE.b.choice]
+title = ""
+story = ""
+options = [
+    { key = "R", label = "", color = "red", target = "a" },
+    { key = "B", label = "", color = "blue", target = "c" },
+]
+
+[FM_NODE.a]
+title = ""
+story = ""
+
+[FM_NODE.a.choice]
+title = ""
+story = ""
+options = [
+    { key = "R", label = "", color = "red", target = "b" },
+    { key = "B", label = "", color = "blue", target = "b" },
+]
+
+[FM_NODE.c]
+title = ""
+story = ""
+"#;
+        let story_data: StoryData = toml::from_str(&fixtures::source(story)).unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+
+        assert_eq!(graph.max_depth, 3);
+        assert!(navigator.state_for_path("BRB").is_some_and(|state| state.is_complete()));
+        assert!(navigator.state_for_path("BR").is_some_and(|state| !state.is_complete()));
+    }
+}
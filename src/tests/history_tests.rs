@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::utils::*;
+    
+    fn completed_run(story_data: &StoryData) -> GameState {
+        let mut game_state = GameState::new();
+        for (i, c) in "RBRBRB".chars().enumerate() {
+            game_state.add_choice(ChoiceType::from_char(c).unwrap());
+            // Skip the gem in the third room
+            if i != 2 {
+                let gem = story_data.get_story_by_path(game_state.get_path()).unwrap().gem.clone().unwrap();
+                game_state.collect_gem(gem);
+            }
+        }
+        game_state
+    }
+    
+    #[test]
+    fn test_history_is_chronological() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let history = HistoryBuilder::build(&StoryGraph::from_story_data(&story_data), &completed_run(&story_data));
+        
+        assert_eq!(history.path, "RBRBRB");
+        assert_eq!(history.entries.len(), 6);
+        assert_eq!(history.gem_count(), 5);
+        assert!(history.entries.iter().any(|entry| entry.path == "RBR" && entry.gem.is_none()));
+        
+        let years: Vec<i32> = history.entries.iter().filter_map(|entry| entry.year).collect();
+        let mut sorted = years.clone();
+        sorted.sort();
+        assert_eq!(years, sorted);
+    }
+    
+    #[test]
+    fn test_history_exports() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let history = HistoryBuilder::build(&StoryGraph::from_story_data(&story_data), &completed_run(&story_data));
+        
+        let markdown = HistoryExporter::to_markdown(&history);
+        assert!(markdown.starts_with("# 人类未来简史"));
+        assert!(markdown.contains(&history.entries[0].title));
+        assert!(markdown.contains(&story_data.fm_noend.title));
+        
+        let html = HistoryExporter::to_html(&history);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("\"盖亚\""));
+        
+        let url = HistoryExporter::data_url("text/markdown", "# 简史 & more");
+        assert_eq!(url, "data:text/markdown;charset=utf-8,%23%20%E7%AE%80%E5%8F%B2%20%26%20more");
+    }
+}
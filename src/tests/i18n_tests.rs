@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::utils::*;
+
+    #[test]
+    fn test_locale_negotiation() {
+        assert_eq!(Locale::from_code("en-GB"), Some(Locale::En));
+        assert_eq!(Locale::from_code("zh_Hans"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_code("fr"), None);
+
+        assert_eq!(Locale::negotiate("fr-FR,en;q=0.8,zh-CN;q=0.9"), Some(Locale::ZhCn));
+        assert_eq!(Locale::negotiate("en-US,en;q=0.9,zh;q=0.8"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("de, en;q=0"), None);
+    }
+
+    #[test]
+    fn test_catalogs_cover_the_same_keys() {
+        let default_keys = I18n::keys(Locale::default());
+        assert!(!default_keys.is_empty());
+        for locale in Locale::ALL {
+            assert_eq!(I18n::keys(locale), default_keys, "{} catalog is out of sync", locale.code());
+        }
+    }
+
+    #[test]
+    fn test_catalog_lookup() {
+        assert_eq!(I18n::t(Locale::ZhCn, "gems.title"), "时间宝石");
+        assert_eq!(I18n::t(Locale::En, "gems.title"), "Time Gems");
+        assert_eq!(I18n::t(Locale::En, "no.such.key"), "no.such.key");
+        assert_eq!(
+            I18n::format(Locale::En, "control.ending_of", &[("count", &64)]),
+            "This is one of 64 possible endings"
+        );
+        assert_eq!(ChoiceType::Other('Q').name(Locale::En), "Option Q");
+    }
+
+    #[test]
+    fn test_english_pack_falls_back_to_default() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let translations = StoryLoader::load_translations("../docs/FM_STORY.toml").unwrap();
+        let english = story_data.localized(&translations[&Locale::En]);
+
+        assert_eq!(english.fm_story["R"].title, "The Dawn of Superintelligence");
+        assert_eq!(english.fm_story["RR"].title, story_data.fm_story["RR"].title);
+        assert_eq!(english.fm_story.len(), story_data.fm_story.len());
+
+        let gem = english.fm_story["R"].gem.as_ref().unwrap();
+        assert!(gem.name.starts_with("Time Gem"));
+        assert_eq!(gem.year, story_data.fm_story["R"].gem.as_ref().unwrap().year);
+
+        let choice = english.get_choice_by_level(1).unwrap();
+        assert_eq!(choice.options[0].label, "Red: SI exists");
+        assert_eq!(choice.options[0].key, 'R');
+        assert_eq!(choice.options[0].color, "red");
+        assert_eq!(StoryGraph::from_story_data(&english).ending_count(), 64);
+
+        let untranslated = translations[&Locale::En].untranslated(&story_data);
+        assert!(untranslated.contains(&"FM_STORY.RR".to_string()));
+        assert!(!untranslated.contains(&"FM_STORY.R".to_string()));
+        assert!(!untranslated.contains(&"FM_START".to_string()));
+        assert_eq!(StoryTranslation::default().untranslated(&story_data).len(), 1 + 1 + 7 + 126);
+    }
+
+    #[test]
+    fn test_tree_renders_in_locale() {
+        let game_state = GameState::from_path("RB", DEFAULT_MAX_DEPTH).unwrap();
+
+        let english = AsciiTreeGenerator::generate_tree(&game_state, Locale::En);
+        assert!(english.starts_with("Story path tree:"));
+        assert!(english.contains("🔵 Blue (Level 2)"));
+
+        let chinese = AsciiTreeGenerator::generate_tree(&game_state, Locale::ZhCn);
+        assert!(chinese.starts_with("故事路径树:"));
+        assert!(chinese.contains("🔴 红色 (Level 1)"));
+    }
+}
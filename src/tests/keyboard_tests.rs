@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::utils::*;
+
+    #[test]
+    fn test_option_keys_choose() {
+        assert_eq!(Shortcut::from_key("R"), Some(Shortcut::Choose(ChoiceType::Red)));
+        assert_eq!(Shortcut::from_key("b"), Some(Shortcut::Choose(ChoiceType::Blue)));
+        assert_eq!(Shortcut::from_key("r"), Shortcut::from_key("R"));
+        assert_eq!(Shortcut::from_key("3"), Some(Shortcut::Choose(ChoiceType::Other('3'))));
+    }
+
+    #[test]
+    fn test_space_and_escape() {
+        assert_eq!(Shortcut::from_key(" "), Some(Shortcut::SkipText));
+        assert_eq!(Shortcut::from_key("Spacebar"), Some(Shortcut::SkipText));
+        assert_eq!(Shortcut::from_key("Escape"), Some(Shortcut::FocusMenu));
+        assert_eq!(Shortcut::from_key("Esc"), Some(Shortcut::FocusMenu));
+    }
+
+    #[test]
+    fn test_other_keys_are_ignored() {
+        assert_eq!(Shortcut::from_key("Enter"), None);
+        assert_eq!(Shortcut::from_key("Tab"), None);
+        assert_eq!(Shortcut::from_key("ArrowLeft"), None);
+        assert_eq!(Shortcut::from_key("?"), None);
+        assert_eq!(Shortcut::from_key(""), None);
+    }
+
+    #[test]
+    fn test_aria_keys() {
+        assert_eq!(Shortcut::Choose(ChoiceType::Red).aria_keys(), "R");
+        assert_eq!(Shortcut::Choose(ChoiceType::Blue).aria_keys(), "B");
+        assert_eq!(Shortcut::SkipText.aria_keys(), "Space");
+        assert_eq!(Shortcut::FocusMenu.aria_keys(), "Escape");
+    }
+
+    #[test]
+    fn test_gem_key_is_free_when_a_room_offers_g() {
+        let option = |key: char, color: &str| ChoiceOption {
+            key,
+            label: key.to_string(),
+            color: color.to_string(),
+            target: None,
+            effects: Default::default(),
+        };
+        let choice = ChoiceData {
+            title: String::new(),
+            story: String::new(),
+            options: vec![option('R', "red"), option('B', "blue"), option('G', "green")],
+        };
+
+        let Some(Shortcut::Choose(choice_type)) = Shortcut::from_key("g") else {
+            panic!("G should choose");
+        };
+        assert_eq!(choice.option(choice_type).map(|option| option.key), Some('G'));
+        assert_eq!(Shortcut::from_key(&GEM_KEY.to_string()), None);
+        assert_eq!(ChoiceType::from_char(GEM_KEY), None);
+    }
+}
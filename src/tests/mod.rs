@@ -1,3 +1,91 @@
 pub mod story_tests;
 pub mod game_state_tests;
-pub mod integration_tests;
\ No newline at end of file
+pub mod integration_tests;
+pub mod validator_tests;
+pub mod history_tests;
+pub mod graph_tests;
+pub mod i18n_tests;
+pub mod reload_tests;
+pub mod authoring_tests;
+pub mod export_tests;
+pub mod streaming_tests;
+pub mod keyboard_tests;
+pub mod ascii_tree_tests;
+pub mod scenario_tests;
+pub mod ending_tests;
+pub mod world_tests;
+pub mod voting_tests;
+pub mod choice_stats_tests;
+pub mod cli_args_tests;
+pub mod static_site_tests;
+
+// Inline stories for tests that need a particular shape; each test keeps only
+// the sections it is about and gets the opening and fallback ending from here
+#[cfg(test)]
+pub mod fixtures {
+    use crate::models::StoryData;
+    use crate::services::StoryValidator;
+
+    pub const FRAME: &str = r#"
+[FM_START]
+title = "未来之门"
+story = "门前"
+
+[FM_NOEND]
+title = "未完待续"
+story = "故事结束"
+"#;
+
+    // Two red/blue levels; RB and BR share a scenario and BB has none
+    pub const TWO_LEVEL: &str = r#"
+[FM_META]
+max_depth = 2
+
+[FM_CHOICE.1]
+title = "第一个选择"
+story = ""
+red = "红"
+blue = "蓝"
+
+[FM_CHOICE.2]
+title = "第二个选择"
+story = ""
+red = "红"
+blue = "蓝"
+
+[FM_STORY.R]
+title = "红"
+story = "红色的路"
+
+[FM_STORY.B]
+title = "蓝"
+story = "蓝色的路"
+
+[FM_STORY.RR]
+title = "守门人"
+story = "结局"
+scenario = "gatekeeper"
+
+[FM_STORY.RB]
+title = "回归"
+story = "结局"
+scenario = "reversion"
+
+[FM_STORY.BR]
+title = "又一次回归"
+story = "结局"
+scenario = "reversion"
+
+[FM_STORY.BB]
+title = "无名"
+story = "结局"
+"#;
+
+    pub fn source(sections: &str) -> String {
+        format!("{}{}", FRAME, sections)
+    }
+
+    pub fn story(sections: &str) -> StoryData {
+        StoryValidator::validate_str(&source(sections)).0.unwrap()
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn shared_story() -> SharedStoryData {
+        SharedStoryData::new(StoryLoader::load_default().unwrap(), HashMap::new())
+    }
+
+    #[test]
+    fn test_replace_swaps_packs_and_bumps_revision() {
+        let story = shared_story();
+        let updates = story.subscribe();
+        assert_eq!(story.revision(), 0);
+
+        let mut edited = StoryLoader::load_default().unwrap();
+        edited.fm_start.title = "Edited".to_string();
+        story.replace(edited, HashMap::new());
+
+        assert_eq!(story.revision(), 1);
+        assert!(updates.has_changed().unwrap());
+        assert_eq!(story.pack(Locale::default()).data.fm_start.title, "Edited");
+        assert_eq!(story.pack(Locale::En).data.fm_start.title, "Edited");
+    }
+
+    #[test]
+    fn test_invalid_reload_keeps_last_good_story() {
+        let story = shared_story();
+        let title = story.pack(Locale::default()).data.fm_start.title.clone();
+
+        let dir = std::env::temp_dir().join(format!("l3_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("FM_STORY.toml");
+        std::fs::write(&path, "[FM_START]\ntitle = \"Broken\"\nstory = \"\"\n").unwrap();
+
+        let result = StoryWatcher::reload(&path, &story);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(StoryLoaderError::Invalid(_))));
+        assert_eq!(story.revision(), 0);
+        assert_eq!(story.pack(Locale::default()).data.fm_start.title, title);
+    }
+
+    #[tokio::test]
+    async fn test_story_events_announce_a_replaced_story() {
+        use axum::{extract::Extension, response::IntoResponse};
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        let story = shared_story();
+        let response = story_events_handler(Extension(story.clone())).await.into_response();
+        let mut body = response.into_body().into_data_stream();
+
+        story.replace(StoryLoader::load_default().unwrap(), HashMap::new());
+        let frame = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .expect("no event after the story was replaced")
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(frame.contains("event: story-updated"), "{}", frame);
+        assert!(frame.contains("data: 1"), "{}", frame);
+    }
+
+    #[test]
+    fn test_watcher_matches_story_and_translation_files() {
+        let story_path = Path::new("../docs/FM_STORY.toml");
+        assert!(StoryWatcher::is_story_file(story_path, Path::new("/srv/docs/FM_STORY.toml")));
+        assert!(StoryWatcher::is_story_file(story_path, Path::new("/srv/docs/FM_STORY.en.toml")));
+        assert!(!StoryWatcher::is_story_file(story_path, Path::new("/srv/docs/FM_STORY.toml.swp")));
+    }
+}
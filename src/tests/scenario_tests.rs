@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::utils::*;
+    use crate::tests::fixtures;
+
+    #[test]
+    fn test_scenario_keys_round_trip() {
+        for scenario in Scenario::ALL {
+            assert_eq!(Scenario::from_key(scenario.key()), Some(scenario));
+        }
+        assert_eq!(Scenario::from_key("skynet"), None);
+
+        let content: StoryContent = toml::from_str("title = \"t\"\nstory = \"s\"\nscenario = \"1984\"").unwrap();
+        assert_eq!(content.scenario, Some(Scenario::NineteenEightyFour));
+    }
+
+    #[test]
+    fn test_every_scenario_is_in_both_catalogs() {
+        for locale in Locale::ALL {
+            for scenario in Scenario::ALL {
+                assert!(!scenario.name(locale).starts_with("scenario."), "{:?} {:?}", locale, scenario);
+                assert!(!scenario.description(locale).starts_with("scenario."), "{:?} {:?}", locale, scenario);
+            }
+        }
+        assert_ne!(Scenario::Gatekeeper.name(Locale::ZhCn), Scenario::Gatekeeper.name(Locale::En));
+    }
+
+    #[test]
+    fn test_default_story_tags_every_ending() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let stats = StoryStats::collect(&story_data);
+
+        assert_eq!(stats.endings.len(), 64);
+        assert!(stats.endings.iter().all(|ending| ending.scenario.is_some()));
+        assert_eq!(stats.scenario_counts().len(), Scenario::ALL.len());
+        assert_eq!(stats.scenario_counts().iter().map(|(_, count)| count).sum::<usize>(), 64);
+    }
+
+    #[test]
+    fn test_stats_count_endings_per_scenario() {
+        let story_data = fixtures::story(fixtures::TWO_LEVEL);
+        let stats = StoryStats::collect(&story_data);
+        let counts = stats.scenario_counts();
+
+        assert_eq!(stats.endings.len(), 4);
+        assert!(counts.contains(&(Some(Scenario::Gatekeeper), 1)));
+        assert!(counts.contains(&(Some(Scenario::Reversion), 2)));
+        assert!(counts.contains(&(Some(Scenario::Conquerors), 0)));
+        assert_eq!(counts.last(), Some(&(None, 1)));
+    }
+
+    #[test]
+    fn test_validator_flags_untagged_and_unknown_scenarios() {
+        let story_data = fixtures::story(fixtures::TWO_LEVEL);
+        let report = StoryValidator::validate(&story_data);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+
+        assert!(kinds.contains(&&DiagnosticKind::MissingScenario("FM_STORY.BB".to_string())));
+        assert!(!kinds.contains(&&DiagnosticKind::MissingScenario("FM_STORY.R".to_string())));
+
+        let (_, report) = StoryValidator::validate_str(&fixtures::source(&fixtures::TWO_LEVEL.replacen("\"reversion\"", "\"skynet\"", 1)));
+        assert!(report.errors().any(|d| d.kind == DiagnosticKind::UnknownScenario {
+            key: "FM_STORY.RB".to_string(),
+            scenario: "skynet".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_ending_node_carries_scenario() {
+        let story_data = fixtures::story(fixtures::TWO_LEVEL);
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        let node = navigator.get_node(&navigator.state_for_path("RB").unwrap());
+
+        assert_eq!(node.story.and_then(|story| story.scenario), Some(Scenario::Reversion));
+        assert!(node.final_story.is_some());
+        assert!(I18n::format(Locale::En, "scenario.led_to", &[("name", &Scenario::Reversion.name(Locale::En))]).contains("Reversion"));
+    }
+}
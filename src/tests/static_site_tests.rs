@@ -0,0 +1,35 @@
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::components::build_static_site;
+    use crate::services::*;
+
+    #[test]
+    fn test_static_site_writes_an_index_per_locale() {
+        let story_path = "../docs/FM_STORY.toml";
+        let story = SharedStoryData::new(
+            StoryLoader::load_from_file(story_path).unwrap(),
+            StoryLoader::load_translations(story_path).unwrap(),
+        );
+
+        let dir = std::env::temp_dir().join(format!("l3_static_{}", std::process::id()));
+        let pages = build_static_site(&story, &dir);
+        let root_index = std::fs::read_to_string(dir.join("index.html"));
+        let en_index = std::fs::read_to_string(dir.join("en").join("index.html"));
+        let has_stylesheet = dir.join("style.css").is_file();
+        let en_pages = std::fs::read_dir(dir.join("en")).map(|entries| entries.count());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let root_index = root_index.unwrap();
+        assert!(root_index.contains(r#"<html lang="zh-CN">"#));
+        assert!(root_index.contains(r#"href="style.css""#));
+        let en_index = en_index.unwrap();
+        assert!(en_index.contains(r#"<html lang="en">"#));
+        assert!(en_index.contains(r#"href="../style.css""#));
+        assert!(has_stylesheet);
+
+        // Every reachable page in both languages
+        let reachable = story.pack(crate::models::Locale::En).navigator().reachable_paths().len();
+        assert_eq!(en_pages.unwrap(), reachable);
+        assert_eq!(pages.unwrap(), reachable * 2);
+    }
+}
@@ -23,20 +23,42 @@ mod tests {
             fm_start: StoryContent {
                 title: "开始".to_string(),
                 story: "故事开始".to_string(),
+                gem: None,
+                scenario: None,
             },
             fm_noend: StoryContent {
                 title: "结束".to_string(),
                 story: "故事结束".to_string(),
+                gem: None,
+                scenario: None,
             },
+            fm_meta: StoryMeta::default(),
+            fm_node: std::collections::HashMap::new(),
+            fm_ending: StoryEndings::default(),
         };
         
-        // Add test story
+        // Add test story; the navigator reaches it through the level-1 choice
+        story_data.fm_choice.insert("1".to_string(), ChoiceData {
+            title: "选择".to_string(),
+            story: "选择说明".to_string(),
+            options: vec![ChoiceOption {
+                key: 'R',
+                label: "红色".to_string(),
+                color: "red".to_string(),
+                target: None,
+                effects: Default::default(),
+            }],
+        });
         story_data.fm_story.insert("R".to_string(), StoryContent {
             title: "红色路径".to_string(),
             story: "选择了红色".to_string(),
+            gem: None,
+            scenario: None,
         });
         
-        let navigator = PathNavigator::new(&story_data);
+        let graph = StoryGraph::from_story_data(&story_data);
+        
+        let navigator = PathNavigator::new(&graph);
         let mut game_state = GameState::new();
         
         // Test initial state
@@ -61,6 +83,70 @@ mod tests {
         
         assert_eq!(ChoiceType::from_char('R'), Some(ChoiceType::Red));
         assert_eq!(ChoiceType::from_char('B'), Some(ChoiceType::Blue));
-        assert_eq!(ChoiceType::from_char('X'), None);
+        assert_eq!(ChoiceType::from_char('G'), Some(ChoiceType::Other('G')));
+        assert_eq!(ChoiceType::from_char('r'), None);
+        assert_eq!(ChoiceType::from_char('?'), None);
+    }
+    
+    #[test]
+    fn test_game_state_from_path() {
+        let game_state = GameState::from_path("RBR", DEFAULT_MAX_DEPTH).unwrap();
+        assert_eq!(game_state.get_path(), "RBR");
+        assert_eq!(game_state.get_level(), 3);
+        
+        assert!(GameState::from_path("", DEFAULT_MAX_DEPTH).is_some());
+        assert!(GameState::from_path("R?", DEFAULT_MAX_DEPTH).is_none());
+        assert!(GameState::from_path("RRRRRRR", DEFAULT_MAX_DEPTH).is_none());
+    }
+    
+    #[test]
+    fn test_story_node_for_path() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        
+        let start = navigator.get_node(&GameState::new());
+        assert_eq!(start.story.unwrap().title, story_data.fm_start.title);
+        assert!(start.choice.is_some());
+        assert!(start.final_story.is_none());
+        
+        let ending = navigator.get_node(&GameState::from_path("RBRBRB", DEFAULT_MAX_DEPTH).unwrap());
+        assert_eq!(ending.path, "RBRBRB");
+        assert!(ending.story.is_some());
+        assert!(ending.choice.is_none());
+        assert_eq!(ending.final_story.unwrap().title, story_data.fm_noend.title);
+    }
+    
+    #[test]
+    fn test_depth_from_story_data() {
+        let mut story_data = StoryLoader::load_default().unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+        assert_eq!(graph.max_depth, 6);
+        assert_eq!(graph.ending_count(), 64);
+        
+        // A declared depth wins over the deepest path in FM_STORY
+        story_data.fm_meta.max_depth = Some(4);
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        let game_state = navigator.state_for_path("RBRB").unwrap();
+        assert!(game_state.is_complete());
+        assert!(navigator.state_for_path("RBRBR").is_none());
+        assert_eq!(graph.ending_count(), 16);
+    }
+    
+    #[test]
+    fn test_choice_leads_into_next_level() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        
+        let start = navigator.get_node(&GameState::new());
+        assert_eq!(start.intro.unwrap().title, story_data.fm_choice["0"].title);
+        assert_eq!(start.choice.unwrap().title, story_data.fm_choice["1"].title);
+        
+        // The last question is asked in a level-5 room
+        let last = navigator.get_node(&navigator.state_for_path("RBRBR").unwrap());
+        assert!(last.intro.is_none());
+        assert_eq!(last.choice.unwrap().title, story_data.fm_choice["6"].title);
     }
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::utils::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_chunks_rebuild_the_text() {
+        let story_data = crate::services::StoryLoader::load_default().unwrap();
+        let text = &story_data.fm_story["RB"].story;
+        let chunks = TextStreamer::default().smart_chunks(text);
+
+        assert!(chunks.len() > 5);
+        assert_eq!(chunks.concat(), *text);
+    }
+
+    #[test]
+    fn test_chunks_break_at_punctuation() {
+        let streamer = TextStreamer::default();
+        assert_eq!(
+            streamer.smart_chunks("人类醒来，机器沉睡。然后"),
+            vec!["人类醒来，", "机器沉睡。", "然后"]
+        );
+        assert_eq!(
+            streamer.smart_chunks("In 2042, Life 3.0 began. Then"),
+            vec!["In 2042,", " Life 3.0 began.", " Then"]
+        );
+    }
+
+    #[test]
+    fn test_chunk_delay_follows_speed() {
+        let chunk = "机器沉睡。";
+        assert_eq!(TextStreamer::default().chunk_delay(chunk), Duration::from_millis(250));
+        assert_eq!(TextStreamer::from_speed(TextSpeed::Fast).chunk_delay(chunk), Duration::from_millis(100));
+        assert_eq!(TextStreamer::from_speed(TextSpeed::Instant).chunk_delay(chunk), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_text_speed_preference() {
+        for speed in TextSpeed::ALL {
+            assert_eq!(TextSpeed::from_key(speed.key()), Some(speed));
+            let stored = serde_json::to_string(&speed).unwrap();
+            assert_eq!(stored, format!("\"{}\"", speed.key()));
+        }
+        assert_eq!(TextSpeed::from_key("warp"), None);
+        assert_eq!(TextSpeed::Fast.name(Locale::En), "Fast");
+        assert_eq!(TextSpeed::default(), TextSpeed::Normal);
+    }
+}
@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use std::collections::HashMap;
+    
+    fn long_story() -> String {
+        "故".repeat(MIN_STORY_CHARS)
+    }
+    
+    fn binary_levels() -> Vec<Vec<ChoiceType>> {
+        vec![vec![ChoiceType::Red, ChoiceType::Blue]; DEFAULT_MAX_DEPTH]
+    }
+    
+    fn option(key: char, label: &str, color: &str) -> ChoiceOption {
+        ChoiceOption {
+            key,
+            label: label.to_string(),
+            color: color.to_string(),
+            target: None,
+            effects: Default::default(),
+        }
+    }
+    
+    fn complete_story_data() -> StoryData {
+        let mut fm_choice = HashMap::new();
+        for level in 0..=DEFAULT_MAX_DEPTH {
+            fm_choice.insert(level.to_string(), ChoiceData {
+                title: format!("选择 {}", level),
+                story: "选择说明".to_string(),
+                options: vec![option('R', "红色", "red"), option('B', "蓝色", "blue")],
+            });
+        }
+        
+        let mut fm_story = HashMap::new();
+        for path in StoryValidator::expected_paths(&binary_levels()) {
+            fm_story.insert(path.clone(), StoryContent {
+                title: path.clone(),
+                story: long_story(),
+                gem: Some(TimeGem {
+                    name: format!("时间宝石·{}", path),
+                    year: 2042 + path.len() as i32 * 1000,
+                    description: "宝石描述".to_string(),
+                }),
+                scenario: (path.len() == binary_levels().len()).then_some(Scenario::Gatekeeper),
+            });
+        }
+        
+        StoryData {
+            fm_choice,
+            fm_story,
+            fm_start: StoryContent {
+                title: "开始".to_string(),
+                story: long_story(),
+                gem: None,
+                scenario: None,
+            },
+            fm_noend: StoryContent {
+                title: "结束".to_string(),
+                story: long_story(),
+                gem: None,
+                scenario: None,
+            },
+            fm_meta: StoryMeta::default(),
+            fm_node: HashMap::new(),
+            fm_ending: StoryEndings::default(),
+        }
+    }
+    
+    #[test]
+    fn test_expected_paths() {
+        let paths = StoryValidator::expected_paths(&binary_levels());
+        
+        assert_eq!(paths.len(), 126);
+        assert_eq!(paths[0], "R");
+        assert_eq!(paths[1], "B");
+        assert!(paths.contains(&"BBBBBB".to_string()));
+    }
+    
+    #[test]
+    fn test_complete_story_is_valid() {
+        let report = StoryValidator::validate(&complete_story_data());
+        
+        assert!(report.is_valid());
+        assert!(report.diagnostics.is_empty());
+    }
+    
+    #[test]
+    fn test_reports_all_problems() {
+        let mut story_data = complete_story_data();
+        story_data.fm_story.remove("RBRB");
+        story_data.fm_story.remove("BB");
+        story_data.fm_story.insert("RX".to_string(), StoryContent {
+            title: "多余".to_string(),
+            story: long_story(),
+            gem: None,
+            scenario: None,
+        });
+        story_data.fm_story.get_mut("R").unwrap().story = "太短".to_string();
+        story_data.fm_story.get_mut("RR").unwrap().gem = None;
+        story_data.fm_choice.remove("3");
+        story_data.fm_choice.insert("7".to_string(), story_data.fm_choice["0"].clone());
+        
+        let report = StoryValidator::validate(&story_data);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+        
+        assert!(!report.is_valid());
+        assert!(kinds.contains(&&DiagnosticKind::MissingPath("RBRB".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::MissingPath("BB".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::ExtraPath("RX".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::MissingChoiceLevel(3)));
+        assert!(kinds.contains(&&DiagnosticKind::UnusedChoiceLevel("7".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::StoryTooShort {
+            key: "FM_STORY.R".to_string(),
+            chars: 2,
+        }));
+        assert!(kinds.contains(&&DiagnosticKind::MissingGem("FM_STORY.RR".to_string())));
+        assert_eq!(report.errors().count(), 3);
+    }
+    
+    #[test]
+    fn test_validate_str_collects_field_errors() {
+        let content = r#"
+[FM_START]
+title = "开始"
+
+[FM_NOEND]
+title = "结束"
+story = "故事结束"
+
+[FM_CHOICE.0]
+title = "选择"
+story = "说明"
+red = "红色"
+
+[FM_STORY.R]
+story = "红色路径"
+"#;
+        let (story_data, report) = StoryValidator::validate_str(content);
+        
+        assert!(story_data.is_none());
+        assert_eq!(report.errors().count(), 3);
+        assert!(report.diagnostics.iter().any(|d| d.kind == DiagnosticKind::InvalidField {
+            key: "FM_CHOICE.0".to_string(),
+            field: "blue".to_string(),
+        }));
+    }
+    
+    #[test]
+    fn test_expected_paths_follow_options() {
+        let levels = vec![
+            vec![ChoiceType::Red, ChoiceType::Other('G'), ChoiceType::Blue],
+            vec![ChoiceType::Red, ChoiceType::Blue],
+        ];
+        let paths = StoryValidator::expected_paths(&levels);
+        
+        assert_eq!(paths.len(), 3 + 6);
+        assert_eq!(&paths[..3], &["R", "G", "B"]);
+        assert!(paths.contains(&"GB".to_string()));
+    }
+    
+    #[test]
+    fn test_reports_bad_options() {
+        let mut story_data = complete_story_data();
+        story_data.fm_choice.get_mut("1").unwrap().options = vec![option('R', "红色", "red")];
+        story_data.fm_choice.get_mut("2").unwrap().options.push(option('r', "小写", "red"));
+        story_data.fm_choice.get_mut("4").unwrap().options.push(option('B', "又一个蓝色", "blue"));
+        
+        let report = StoryValidator::validate(&story_data);
+        let kinds: Vec<&DiagnosticKind> = report.diagnostics.iter().map(|d| &d.kind).collect();
+        
+        assert!(kinds.contains(&&DiagnosticKind::TooFewOptions("FM_CHOICE.1".to_string())));
+        assert!(kinds.contains(&&DiagnosticKind::InvalidOption {
+            key: "FM_CHOICE.2".to_string(),
+            option: 'r',
+        }));
+        assert!(kinds.contains(&&DiagnosticKind::DuplicateOption {
+            key: "FM_CHOICE.4".to_string(),
+            option: 'B',
+        }));
+    }
+    
+    #[test]
+    fn test_validate_str_accepts_options() {
+        let mut content = String::from(r#"
+[FM_META]
+max_depth = 1
+
+[FM_START]
+title = "开始"
+story = "故事开始"
+
+[FM_NOEND]
+title = "结束"
+story = "故事结束"
+
+[FM_CHOICE.1]
+title = "选择"
+story = "说明"
+options = [
+    { key = "R", label = "红色", color = "red" },
+    { key = "G", label = "绿色", color = "green" },
+    { key = "B", label = "蓝色", color = "blue" },
+]
+"#);
+        for path in ["R", "G", "B"] {
+            content.push_str(&format!("\n[FM_STORY.{}]\ntitle = \"{}\"\nstory = \"{}\"\n", path, path, long_story()));
+        }
+        let (story_data, report) = StoryValidator::validate_str(&content);
+        let story_data = story_data.unwrap();
+        
+        assert!(report.is_valid());
+        assert_eq!(StoryGraph::from_story_data(&story_data).ending_count(), 3);
+        assert_eq!(story_data.get_choice_by_level(1).unwrap().options[1].label, "绿色");
+    }
+    
+    #[test]
+    fn test_validate_str_syntax_error() {
+        let (story_data, report) = StoryValidator::validate_str("[FM_START\ntitle = ");
+        
+        assert!(story_data.is_none());
+        assert!(matches!(report.diagnostics[0].kind, DiagnosticKind::Syntax(_)));
+    }
+
+    #[test]
+    fn test_opening_and_fallback_ending_need_the_minimum_length() {
+        let content = format!(r#"
+[FM_START]
+title = "未来之门"
+story = "门前"
+
+[FM_NOEND]
+title = "未完待续"
+story = "{}"
+
+[FM_META]
+max_depth = 1
+
+[FM_CHOICE.1]
+title = "选择"
+story = ""
+red = "红"
+blue = "蓝"
+
+[FM_STORY.R]
+title = "红"
+story = "{}"
+gem = {{ name = "宝石", year = 2045, description = "" }}
+scenario = "gatekeeper"
+
+[FM_STORY.B]
+title = "蓝"
+story = "{}"
+gem = {{ name = "宝石", year = 2046, description = "" }}
+scenario = "reversion"
+"#, long_story(), long_story(), long_story());
+        let (_, report) = StoryValidator::validate_str(&content);
+        
+        assert!(report.is_valid());
+        let warnings: Vec<&DiagnosticKind> = report.warnings().map(|d| &d.kind).collect();
+        assert_eq!(warnings, vec![&DiagnosticKind::StoryTooShort {
+            key: "FM_START".to_string(),
+            chars: 2,
+        }]);
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+
+    fn two_way_choice() -> ChoiceData {
+        let option = |key: char, label: &str, color: &str| ChoiceOption {
+            key,
+            label: label.to_string(),
+            color: color.to_string(),
+            target: None,
+            effects: Default::default(),
+        };
+        ChoiceData {
+            title: "第一步".to_string(),
+            story: String::new(),
+            options: vec![option('R', "加速", "red"), option('B', "暂停", "blue")],
+        }
+    }
+
+    #[test]
+    fn test_join_codes_are_readable() {
+        for _ in 0..50 {
+            let code = join_code();
+            assert_eq!(code.len(), JOIN_CODE_LEN);
+            assert!(!code.contains(['0', 'O', '1', 'I']), "{}", code);
+            assert_eq!(normalize_join_code(&code), Some(code));
+        }
+    }
+
+    #[test]
+    fn test_normalize_join_code() {
+        assert_eq!(normalize_join_code(" ab 2c "), Some("AB2C".to_string()));
+        assert_eq!(normalize_join_code("AB2"), None);
+        assert_eq!(normalize_join_code("AB2CD"), None);
+        assert_eq!(normalize_join_code("AB0C"), None);
+        assert_eq!(normalize_join_code(""), None);
+    }
+
+    #[test]
+    fn test_voters_can_change_their_vote() {
+        let mut round = VoteRound::new(&two_way_choice(), 10);
+        assert!(round.cast(1, 'R'));
+        assert!(round.cast(2, 'R'));
+        assert!(round.cast(1, 'B'));
+        assert!(!round.cast(3, 'X'));
+
+        assert_eq!(round.tallies(), vec![('R', 1), ('B', 1)]);
+    }
+
+    #[test]
+    fn test_majority_breaks_ties_by_option_order() {
+        let mut round = VoteRound::new(&two_way_choice(), 10);
+        assert_eq!(round.majority(), None);
+
+        round.cast(1, 'B');
+        assert_eq!(round.majority(), Some('B'));
+        round.cast(2, 'R');
+        assert_eq!(round.majority(), Some('R'));
+        round.cast(3, 'B');
+        assert_eq!(round.majority(), Some('B'));
+    }
+
+    #[test]
+    fn test_round_counts_down() {
+        let mut round = VoteRound::new(&two_way_choice(), 2);
+        assert!(round.tick());
+        assert!(!round.tick());
+        assert!(!round.cast(1, 'R'));
+
+        assert_eq!(VoteRound::new(&two_way_choice(), 0).seconds_left, 1);
+        assert_eq!(VoteRound::new(&two_way_choice(), 10_000).seconds_left, MAX_VOTE_SECONDS);
+    }
+
+    #[test]
+    fn test_socket_messages_are_tagged_json() {
+        let vote: VoteRequest = serde_json::from_str(r#"{"type":"vote","key":"R"}"#).unwrap();
+        assert_eq!(vote, VoteRequest::Vote { key: 'R' });
+        let open: VoteRequest = serde_json::from_str(r#"{"type":"open","path":"RB","seconds":30}"#).unwrap();
+        assert_eq!(open, VoteRequest::Open { path: "RB".to_string(), seconds: 30 });
+        assert_eq!(serde_json::to_string(&VoteRequest::Close).unwrap(), r#"{"type":"close"}"#);
+
+        let view = VoteRoomView {
+            code: "AB2C".to_string(),
+            path: String::new(),
+            choice: None,
+            phase: VotePhase::Open { seconds_left: 5 },
+            tallies: vec![('R', 2), ('B', 1)],
+            voters: 4,
+        };
+        let json = serde_json::to_string(&view).unwrap();
+        assert!(json.contains(r#""phase":{"phase":"open","seconds_left":5}"#), "{}", json);
+        let back: VoteRoomView = serde_json::from_str(&json).unwrap();
+        assert!(back.is_open());
+        assert_eq!(back.total_votes(), 3);
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod room_tests {
+    use crate::models::*;
+    use crate::services::*;
+
+    fn graph() -> StoryGraph {
+        StoryGraph::from_story_data(&StoryLoader::load_default().unwrap())
+    }
+
+    #[test]
+    fn test_majority_moves_the_shared_game_on() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let rooms = VotingRooms::default();
+        let room = rooms.create(Locale::default(), navigator.graph().max_depth).unwrap();
+        assert!(rooms.get(&room.code).is_some());
+
+        let (alice, bob, carol) = (room.join(), room.join(), room.join());
+        assert_eq!(room.view().voters, 3);
+        assert_eq!(room.cast(alice, 'R'), Err(VoteError::NotOpen));
+
+        room.open(&navigator, "", 30).unwrap();
+        room.cast(alice, 'B').unwrap();
+        room.cast(bob, 'R').unwrap();
+        room.cast(carol, 'B').unwrap();
+        assert_eq!(room.cast(carol, 'X'), Err(VoteError::InvalidOption('X')));
+        assert_eq!(room.view().tallies, vec![('R', 1), ('B', 2)]);
+
+        assert_eq!(room.close(&navigator), Some('B'));
+        let view = room.view();
+        assert_eq!(view.path, "B");
+        assert_eq!(view.phase, VotePhase::Decided { key: Some('B') });
+        assert_eq!(view.total_votes(), 3);
+        assert_eq!(room.game_state().get_path(), "B");
+        assert_eq!(room.cast(alice, 'R'), Err(VoteError::NotOpen));
+    }
+
+    #[test]
+    fn test_countdown_closes_its_own_round_only() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let room = VotingRooms::default().create(Locale::default(), navigator.graph().max_depth).unwrap();
+
+        let first = room.open(&navigator, "R", 1).unwrap();
+        let second = room.open(&navigator, "R", 2).unwrap();
+        assert!(!room.tick(&navigator, first));
+        assert!(room.view().is_open());
+
+        assert!(room.tick(&navigator, second));
+        assert_eq!(room.view().phase, VotePhase::Open { seconds_left: 1 });
+        assert!(!room.tick(&navigator, second));
+        // Nobody voted, so the game stays put
+        assert_eq!(room.view().phase, VotePhase::Decided { key: None });
+        assert_eq!(room.view().path, "R");
+    }
+
+    #[test]
+    fn test_open_needs_a_choice_and_remove_ends_the_room() {
+        let graph = graph();
+        let navigator = PathNavigator::new(&graph);
+        let rooms = VotingRooms::default();
+        let room = rooms.create(Locale::default(), navigator.graph().max_depth).unwrap();
+
+        assert_eq!(room.open(&navigator, "Q", 30), Err(VoteError::NoChoice("Q".to_string())));
+        let ending = "R".repeat(navigator.graph().max_depth);
+        assert_eq!(room.open(&navigator, &ending, 30), Err(VoteError::NoChoice(ending.clone())));
+
+        let updates = room.subscribe();
+        rooms.remove(&room.code);
+        assert!(rooms.get(&room.code).is_none());
+        assert_eq!(updates.borrow().phase, VotePhase::Ended);
+    }
+
+    type Client = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+    // The voting routes on a free local port, as the server mounts them
+    async fn serve_voting() -> std::net::SocketAddr {
+        use axum::{routing::get, Extension, Router};
+
+        let story = SharedStoryData::new(StoryLoader::load_default().unwrap(), Default::default());
+        let app = Router::new()
+            .route("/ws/vote", get(vote_host_handler))
+            .route("/ws/vote/:code", get(vote_join_handler))
+            .layer(Extension(story))
+            .layer(Extension(VotingRooms::default()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    async fn send(client: &mut Client, request: VoteRequest) {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        client.send(Message::Text(serde_json::to_string(&request).unwrap())).await.unwrap();
+    }
+
+    // Skips views until one matches, since every change in the room is published
+    async fn view_where(client: &mut Client, matches: impl Fn(&VoteRoomView) -> bool) -> VoteRoomView {
+        use futures::StreamExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                if let Message::Text(text) = client.next().await.unwrap().unwrap() {
+                    let view: VoteRoomView = serde_json::from_str(&text).unwrap();
+                    if matches(&view) {
+                        return view;
+                    }
+                }
+            }
+        })
+        .await
+        .expect("the room never reached the expected view")
+    }
+
+    #[tokio::test]
+    async fn test_sockets_host_join_vote_and_decide() {
+        use tokio_tungstenite::connect_async;
+
+        let addr = serve_voting().await;
+        let (mut host, _) = connect_async(format!("ws://{}/ws/vote", addr)).await.unwrap();
+        let code = view_where(&mut host, |_| true).await.code;
+        assert!(connect_async(format!("ws://{}/ws/vote/ZZZZ", addr)).await.is_err());
+
+        let (mut phone, _) = connect_async(format!("ws://{}/ws/vote/{}", addr, code.to_lowercase())).await.unwrap();
+        view_where(&mut host, |view| view.voters == 1).await;
+
+        // Only the presenter opens votes and only the audience casts them
+        send(&mut phone, VoteRequest::Open { path: "B".to_string(), seconds: 30 }).await;
+        send(&mut host, VoteRequest::Open { path: String::new(), seconds: 30 }).await;
+        let open = view_where(&mut phone, VoteRoomView::is_open).await;
+        assert_eq!(open.path, "");
+        send(&mut host, VoteRequest::Vote { key: 'B' }).await;
+        send(&mut phone, VoteRequest::Vote { key: 'R' }).await;
+        let voted = view_where(&mut host, |view| view.total_votes() == 1).await;
+        assert_eq!(voted.tallies, vec![('R', 1), ('B', 0)]);
+
+        send(&mut host, VoteRequest::Close).await;
+        let decided = view_where(&mut phone, |view| !view.is_open()).await;
+        assert_eq!(decided.phase, VotePhase::Decided { key: Some('R') });
+        assert_eq!(decided.path, "R");
+
+        // The room closes with its presenter
+        drop(host);
+        view_where(&mut phone, |view| view.phase == VotePhase::Ended).await;
+    }
+}
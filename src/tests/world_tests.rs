@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::*;
+    use crate::services::*;
+    use crate::tests::fixtures;
+    use crate::utils::*;
+    use std::collections::BTreeMap;
+
+    const EFFECT_STORY: &str = r#"
+[FM_NODE.start]
+title = "起点"
+story = "开始"
+
+[FM_NODE.start.choice]
+title = "第一步"
+story = ""
+options = [
+    { key = "R", label = "加速", color = "red", target = "fast", effects = { superintelligence = 2, safety = -1 } },
+    { key = "B", label = "暂停", color = "blue", target = "slow", effects = { safety = 1 } },
+]
+
+[FM_NODE.fast]
+title = "加速"
+story = "快"
+
+[FM_NODE.fast.choice]
+title = "第二步"
+story = ""
+options = [
+    { key = "R", label = "继续", color = "red", target = "end", effects = { superintelligence = 1 } },
+    { key = "B", label = "刹车", color = "blue", target = "end", effects = { safety = 2, happiness = 1 } },
+]
+
+[FM_NODE.slow]
+title = "暂停"
+story = "慢"
+
+[FM_NODE.end]
+title = "终点"
+story = "结局"
+"#;
+
+    fn effect_story() -> StoryData {
+        let (story_data, report) = StoryValidator::validate_str(&fixtures::source(EFFECT_STORY));
+        assert!(report.is_valid(), "{:?}", report.errors().collect::<Vec<_>>());
+        story_data.unwrap()
+    }
+
+    #[test]
+    fn test_game_state_accumulates_effects() {
+        let mut state = GameState::new();
+        state.apply_effects(&BTreeMap::from([("safety".to_string(), 1), ("happiness".to_string(), -2)]));
+        state.apply_effects(&BTreeMap::from([("safety".to_string(), 2)]));
+
+        assert_eq!(state.axis_value("safety"), 3);
+        assert_eq!(state.axis_value("happiness"), -2);
+        assert_eq!(state.axis_value("survival"), 0);
+        assert_eq!(state.world.len(), 2);
+
+        state.reset();
+        assert!(state.world.is_empty());
+    }
+
+    #[test]
+    fn test_saves_without_world_still_load() {
+        let json = r#"{"choice_path":"R","current_level":1,"choices":[{"choice_type":"Red","level":0}]}"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert!(state.world.is_empty());
+    }
+
+    #[test]
+    fn test_navigator_applies_effects_along_the_path() {
+        let graph = StoryGraph::from_story_data(&effect_story());
+        let navigator = PathNavigator::new(&graph);
+
+        let state = navigator.state_for_path("RB").unwrap();
+        assert_eq!(state.world, vec![
+            ("safety".to_string(), 1),
+            ("superintelligence".to_string(), 2),
+            ("happiness".to_string(), 1),
+        ]);
+        assert!(navigator.state_for_path("").unwrap().world.is_empty());
+
+        let node = navigator.get_node(&state);
+        assert_eq!(node.world, state.world);
+        assert_eq!(node.axes.len(), 3);
+    }
+
+    #[test]
+    fn test_axes_span_every_run() {
+        let graph = StoryGraph::from_story_data(&effect_story());
+        let axes = graph.axes();
+        let axis = |name: &str| axes.iter().find(|axis| axis.name == name).unwrap().clone();
+
+        // Nearest rooms first: the start's options, then those one step in
+        assert_eq!(axes.iter().map(|axis| axis.name.as_str()).collect::<Vec<_>>(), ["safety", "superintelligence", "happiness"]);
+        assert_eq!((axis("superintelligence").min, axis("superintelligence").max), (0, 3));
+        assert_eq!((axis("safety").min, axis("safety").max), (-1, 1));
+        assert_eq!((axis("happiness").min, axis("happiness").max), (0, 1));
+    }
+
+    #[test]
+    fn test_default_story_moves_six_axes() {
+        let story_data = StoryLoader::load_default().unwrap();
+        let graph = StoryGraph::from_story_data(&story_data);
+        let navigator = PathNavigator::new(&graph);
+        let axes = navigator.graph().axes();
+
+        assert_eq!(
+            axes.iter().map(|axis| axis.name.as_str()).collect::<Vec<_>>(),
+            ["superintelligence", "dominance", "safety", "happiness", "consciousness", "survival"]
+        );
+        assert!(axes.iter().all(|axis| axis.min == -1 && axis.max == 1));
+        for locale in Locale::ALL {
+            assert!(axes.iter().all(|axis| axis.label(locale) != axis.name));
+        }
+
+        let state = navigator.state_for_path("RBRBRB").unwrap();
+        assert_eq!(state.axis_value("superintelligence"), 1);
+        assert_eq!(state.axis_value("dominance"), -1);
+        assert_eq!(state.axis_value("survival"), -1);
+    }
+
+    #[test]
+    fn test_axis_fraction_and_label() {
+        let axis = Axis { name: "optimism".to_string(), min: -2, max: 2 };
+        assert_eq!(axis.fraction(0), 0.5);
+        assert_eq!(axis.fraction(2), 1.0);
+        assert_eq!(axis.fraction(-5), 0.0);
+        assert_eq!(axis.label(Locale::En), "optimism");
+        assert_eq!(Axis { name: "flat".to_string(), min: 0, max: 0 }.fraction(0), 0.5);
+        assert_eq!(Axis::name_label("safety", Locale::En), "Safety");
+    }
+
+    #[test]
+    fn test_statistics_list_world_state() {
+        let graph = StoryGraph::from_story_data(&effect_story());
+        let navigator = PathNavigator::new(&graph);
+        let state = navigator.state_for_path("R").unwrap();
+        let stats = AsciiTreeGenerator::generate_statistics(&state, Locale::En);
+
+        assert!(stats.contains("World state:"));
+        assert!(stats.contains("Superintelligence: +2"));
+        assert!(stats.contains("Safety: -1"));
+        assert!(!AsciiTreeGenerator::generate_statistics(&GameState::new(), Locale::En).contains("World state:"));
+    }
+
+    #[test]
+    fn test_validator_rejects_bad_effects() {
+        let content = fixtures::source(&EFFECT_STORY.replace("effects = { safety = 1 }", "effects = { safety = \"up\" }"));
+        let (_, report) = StoryValidator::validate_str(&content);
+        assert!(report.errors().any(|d| d.kind == DiagnosticKind::InvalidField {
+            key: "FM_NODE.start.choice.options[1]".to_string(),
+            field: "effects".to_string(),
+        }));
+
+        let (story_data, report) = StoryValidator::validate_str(&std::fs::read_to_string("../docs/FM_STORY.toml").unwrap());
+        assert!(report.is_valid());
+        let choice = story_data.unwrap().get_choice_by_level(3).cloned().unwrap();
+        assert_eq!(choice.options[0].effects, BTreeMap::from([("safety".to_string(), 1)]));
+    }
+}
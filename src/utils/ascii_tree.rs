@@ -1,46 +1,72 @@
-use crate::models::GameState;
+use crate::models::{Axis, ChoiceType, GameState, Locale, StoryGraph};
+use unicode_width::UnicodeWidthStr;
+use crate::utils::I18n;
+
+// How much of the story `generate_full_tree` draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeOptions {
+    // Levels below the start; deeper branches end in …
+    pub depth: usize,
+    // Only rooms along the current path open up; the rest stay folded
+    pub collapse: bool,
+    pub titles: bool,
+}
+
+impl TreeOptions {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            collapse: true,
+            titles: false,
+        }
+    }
+    
+    // Whether a room at `level` that has choices shows them
+    pub fn opens(&self, level: usize, on_path: bool) -> bool {
+        level < self.depth && (on_path || !self.collapse)
+    }
+}
 
 pub struct AsciiTreeGenerator;
 
 impl AsciiTreeGenerator {
-    pub fn generate_tree(game_state: &GameState) -> String {
+    pub fn generate_tree(game_state: &GameState, locale: Locale) -> String {
         let mut tree = String::new();
         let path = game_state.get_path();
         
-        tree.push_str("故事路径树:\n");
+        tree.push_str(&format!("{}\n", I18n::t(locale, "tree.title")));
         tree.push_str("════════════\n");
         
         // Root node
-        tree.push_str("📚 开始\n");
+        tree.push_str(&format!("📚 {}\n", I18n::t(locale, "tree.start")));
         
         // Generate tree for each level
-        for level in 0..6 {
+        for level in 0..game_state.get_max_depth() {
             let indent = "  ".repeat(level + 1);
+            let level_label = I18n::format(locale, "tree.level", &[("level", &(level + 1))]);
             
             if level < path.len() {
-                let choice_char = path.chars().nth(level).unwrap();
-                let (icon, name) = match choice_char {
-                    'R' => ("🔴", "红色"),
-                    'B' => ("🔵", "蓝色"),
-                    _ => ("❓", "未知"),
+                let (icon, name) = match path.chars().nth(level).and_then(ChoiceType::from_char) {
+                    Some(choice) => (choice.icon(), choice.name(locale)),
+                    None => ("❓", I18n::t(locale, "tree.unknown").to_string()),
                 };
                 
-                tree.push_str(&format!("{}├─ {} {} (Level {})\n", 
-                    indent, icon, name, level + 1));
+                tree.push_str(&format!("{}├─ {} {} ({})\n", 
+                    indent, icon, name, level_label));
             } else if level == path.len() && game_state.can_make_choice() {
-                tree.push_str(&format!("{}├─ ❓ [当前选择] (Level {})\n", 
-                    indent, level + 1));
+                tree.push_str(&format!("{}├─ ❓ {} ({})\n", 
+                    indent, I18n::t(locale, "tree.current"), level_label));
                 break;
             } else {
-                tree.push_str(&format!("{}├─ ⚪ [未选择] (Level {})\n", 
-                    indent, level + 1));
+                tree.push_str(&format!("{}├─ ⚪ {} ({})\n", 
+                    indent, I18n::t(locale, "tree.unchosen"), level_label));
             }
         }
         
         // Add completion indicator
         if game_state.is_complete() {
-            tree.push_str("  └─ 🎯 故事完成!\n");
-            tree.push_str(&format!("     完整路径: {}\n", path));
+            tree.push_str(&format!("  └─ 🎯 {}\n", I18n::t(locale, "tree.complete")));
+            tree.push_str(&format!("     {}\n", I18n::format(locale, "tree.full_path", &[("path", &path)])));
         }
         
         tree.push_str("════════════\n");
@@ -53,15 +79,12 @@ impl AsciiTreeGenerator {
         
         tree.push_str("Path: ");
         if path.is_empty() {
-            tree.push_str("📚");
+            tree.push('📚');
         } else {
-            tree.push_str("📚");
+            tree.push('📚');
             for c in path.chars() {
-                match c {
-                    'R' => tree.push_str(" → 🔴"),
-                    'B' => tree.push_str(" → 🔵"),
-                    _ => tree.push_str(" → ❓"),
-                }
+                let icon = ChoiceType::from_char(c).map_or("❓", |choice| choice.icon());
+                tree.push_str(&format!(" → {}", icon));
             }
         }
         
@@ -72,34 +95,127 @@ impl AsciiTreeGenerator {
         tree
     }
     
-    pub fn generate_statistics(game_state: &GameState) -> String {
+    pub fn generate_statistics(game_state: &GameState, locale: Locale) -> String {
         let mut stats = String::new();
         
-        stats.push_str("游戏统计:\n");
+        stats.push_str(&format!("{}\n", I18n::t(locale, "tree.stats")));
         stats.push_str("────────────\n");
-        stats.push_str(&format!("当前层级: {}/6\n", game_state.get_level()));
-        stats.push_str(&format!("选择路径: {}\n", 
-            if game_state.get_path().is_empty() { 
-                "无" 
-            } else { 
-                game_state.get_path() 
-            }));
-        stats.push_str(&format!("进度: {:.1}%\n", 
-            (game_state.get_level() as f32 / 6.0) * 100.0));
+        stats.push_str(&format!("{}\n", I18n::format(locale, "tree.stats_level", &[
+            ("level", &game_state.get_level()),
+            ("max", &game_state.get_max_depth()),
+        ])));
+        let path = if game_state.get_path().is_empty() { 
+            I18n::t(locale, "tree.no_path") 
+        } else { 
+            game_state.get_path() 
+        };
+        stats.push_str(&format!("{}\n", I18n::format(locale, "tree.stats_path", &[("path", &path)])));
+        let percent = format!("{:.1}", 
+            (game_state.get_level() as f32 / game_state.get_max_depth().max(1) as f32) * 100.0);
+        stats.push_str(&format!("{}\n", I18n::format(locale, "tree.stats_progress", &[("percent", &percent)])));
         
-        let red_count = game_state.get_path().chars().filter(|&c| c == 'R').count();
-        let blue_count = game_state.get_path().chars().filter(|&c| c == 'B').count();
+        // Count each option in the order it was first picked, red and blue always shown
+        let mut counts: Vec<(ChoiceType, usize)> = vec![(ChoiceType::Red, 0), (ChoiceType::Blue, 0)];
+        for choice in &game_state.choices {
+            match counts.iter_mut().find(|(choice_type, _)| *choice_type == choice.choice_type) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((choice.choice_type, 1)),
+            }
+        }
+        for (choice_type, count) in counts {
+            stats.push_str(&format!("{}\n", I18n::format(locale, "tree.choice_count", &[
+                ("name", &choice_type.name(locale)),
+                ("count", &count),
+            ])));
+        }
         
-        stats.push_str(&format!("红色选择: {}\n", red_count));
-        stats.push_str(&format!("蓝色选择: {}\n", blue_count));
+        if !game_state.world.is_empty() {
+            stats.push_str(&format!("{}\n", I18n::t(locale, "tree.world")));
+            for (axis, value) in &game_state.world {
+                stats.push_str(&format!("{}\n", I18n::format(locale, "tree.axis_value", &[
+                    ("name", &Axis::name_label(axis, locale)),
+                    ("value", &format!("{:+}", value)),
+                ])));
+            }
+        }
         
         if game_state.is_complete() {
-            stats.push_str("状态: 完成 ✅\n");
+            stats.push_str(&format!("{}\n", I18n::t(locale, "tree.status_complete")));
         } else {
-            stats.push_str("状态: 进行中 ⏳\n");
+            stats.push_str(&format!("{}\n", I18n::t(locale, "tree.status_active")));
         }
         
         stats.push_str("────────────\n");
         stats
     }
-}
\ No newline at end of file
+    
+    // Two panels next to each other on a terminal; CJK and emoji take two columns
+    pub fn side_by_side(left: &str, right: &str, gap: usize) -> String {
+        let left_lines: Vec<&str> = left.lines().collect();
+        let right_lines: Vec<&str> = right.lines().collect();
+        let width = left_lines.iter().map(|line| line.width()).max().unwrap_or(0);
+        let mut panels = String::new();
+        
+        for i in 0..left_lines.len().max(right_lines.len()) {
+            let left_line = left_lines.get(i).copied().unwrap_or_default();
+            panels.push_str(left_line);
+            if let Some(right_line) = right_lines.get(i) {
+                panels.push_str(&" ".repeat(width - left_line.width() + gap));
+                panels.push_str(right_line);
+            }
+            panels.push('\n');
+        }
+        panels
+    }
+    
+    // Every run from the start as a tree of choice paths, so a room reached two
+    // ways shows up twice; ● marks the way taken and ◀ where the player stands
+    pub fn generate_full_tree(graph: &StoryGraph, current_path: &str, options: TreeOptions) -> String {
+        let mut tree = String::new();
+        let Some(root) = graph.node(&graph.start) else {
+            return tree;
+        };
+        let marker = if current_path.is_empty() { " ◀" } else { "" };
+        let more = if options.depth == 0 && !root.is_ending() { " …" } else { "" };
+        tree.push_str(&format!("📚 {}{}{}\n", root.content.title, more, marker));
+        if options.depth > 0 {
+            Self::push_paths(&mut tree, graph, &graph.start, "", "", current_path, options);
+        }
+        tree
+    }
+    
+    fn push_paths(tree: &mut String, graph: &StoryGraph, id: &str, path: &str, prefix: &str, current_path: &str, options: TreeOptions) {
+        let Some(choice) = graph.node(id).and_then(|node| node.choice.as_ref()) else {
+            return;
+        };
+        for (i, option) in choice.options.iter().enumerate() {
+            let last = i + 1 == choice.options.len();
+            let branch = if last { "└─" } else { "├─" };
+            let icon = option.choice_type().map_or("❓", |choice| choice.icon());
+            let target = option.target.as_deref().unwrap_or_default();
+            let child_path = format!("{}{}", path, option.key);
+            let Some(node) = graph.node(target) else {
+                tree.push_str(&format!("{}{} {} {} ✗ {}\n", prefix, branch, icon, child_path, target));
+                continue;
+            };
+            
+            let level = child_path.chars().count();
+            let on_path = current_path.starts_with(&child_path);
+            let has_children = !node.is_ending() && level < graph.max_depth;
+            let open = has_children && options.opens(level, on_path);
+            let title = if options.titles { format!(" {}", node.content.title) } else { String::new() };
+            let more = if has_children && !open { " …" } else { "" };
+            let marker = match (on_path, child_path.len() == current_path.len()) {
+                (true, true) => " ◀",
+                (true, false) => " ●",
+                _ => "",
+            };
+            tree.push_str(&format!("{}{} {} {}{}{}{}\n", prefix, branch, icon, child_path, title, more, marker));
+            
+            if open {
+                let prefix = format!("{}{}", prefix, if last { "   " } else { "│  " });
+                Self::push_paths(tree, graph, target, &child_path, &prefix, current_path, options);
+            }
+        }
+    }
+}
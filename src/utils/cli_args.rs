@@ -0,0 +1,20 @@
+// Argument helpers shared by the command-line tools
+
+// Removes `--name value` from the arguments
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err(format!("{} needs a value", name));
+    }
+    args.remove(i);
+    Ok(Some(args.remove(i)))
+}
+
+// Removes every `--name` from the arguments and tells whether there was one
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != before
+}
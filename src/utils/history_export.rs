@@ -0,0 +1,141 @@
+use crate::models::{FutureHistory, HistoryEntry, Locale};
+use crate::utils::I18n;
+
+pub struct HistoryExporter;
+
+impl HistoryExporter {
+    pub fn to_markdown(history: &FutureHistory) -> String {
+        let locale = history.locale;
+        let mut doc = String::new();
+
+        doc.push_str(&format!("# {}\n\n", I18n::t(locale, "history.title")));
+        doc.push_str(&format!("> {}\n\n", summary(history)));
+
+        doc.push_str(&format!(
+            "## {} · {}\n\n{}\n\n",
+            I18n::t(locale, "history.prologue"),
+            history.prologue.title,
+            history.prologue.story
+        ));
+
+        for entry in &history.entries {
+            let year = Self::year_label(entry, locale);
+            doc.push_str(&format!("## {} · {}\n\n", year, entry.title));
+            if let Some(gem) = &entry.gem {
+                doc.push_str(&format!("> 💎 {}: {}\n\n", gem.name, gem.description));
+            }
+            doc.push_str(&format!("{}\n\n", entry.story));
+        }
+
+        doc.push_str(&format!(
+            "## {} · {}\n\n{}\n",
+            I18n::t(locale, "history.epilogue"),
+            history.epilogue.title,
+            history.epilogue.story
+        ));
+        doc
+    }
+
+    pub fn to_html(history: &FutureHistory) -> String {
+        let locale = history.locale;
+        let title = I18n::t(locale, "history.title");
+        let mut body = String::new();
+
+        body.push_str(&format!("<p class=\"meta\">{}</p>\n", escape_html(&summary(history))));
+        body.push_str(&section(
+            I18n::t(locale, "history.prologue"),
+            &history.prologue.title,
+            None,
+            &history.prologue.story,
+        ));
+
+        for entry in &history.entries {
+            let year = Self::year_label(entry, locale);
+            let gem = entry.gem.as_ref().map(|gem| format!("💎 {}: {}", gem.name, gem.description));
+            body.push_str(&section(&year, &entry.title, gem.as_deref(), &entry.story));
+        }
+
+        body.push_str(&section(
+            I18n::t(locale, "history.epilogue"),
+            &history.epilogue.title,
+            None,
+            &history.epilogue.story,
+        ));
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{} · {}</title>\n<style>\n{}</style>\n</head>\n<body>\n\
+             <h1>{}</h1>\n{}</body>\n</html>\n",
+            locale.code(),
+            title,
+            escape_html(&history.path),
+            HISTORY_CSS,
+            title,
+            body
+        )
+    }
+
+    pub fn year_label(entry: &HistoryEntry, locale: Locale) -> String {
+        match entry.year {
+            Some(year) => I18n::format(locale, "history.year", &[("year", &year)]),
+            None => I18n::t(locale, "history.year_unknown").to_string(),
+        }
+    }
+
+    pub fn data_url(mime: &str, content: &str) -> String {
+        format!("data:{};charset=utf-8,{}", mime, percent_encode(content))
+    }
+}
+
+const HISTORY_CSS: &str = "body { max-width: 42em; margin: 2em auto; padding: 0 1em; font-family: serif; line-height: 1.7; }
+h2 { border-bottom: 1px solid #ccc; }
+.meta { color: #666; }
+.gem { background: #f3f0ff; border-left: 4px solid #845ef7; padding: 0.5em 1em; }
+";
+
+fn summary(history: &FutureHistory) -> String {
+    I18n::format(history.locale, "history.summary", &[
+        ("path", &history.path),
+        ("gems", &history.gem_count()),
+        ("total", &history.entries.len()),
+    ])
+}
+
+fn section(heading: &str, title: &str, gem: Option<&str>, story: &str) -> String {
+    let mut html = format!("<section>\n<h2>{} · {}</h2>\n", escape_html(heading), escape_html(title));
+    if let Some(gem) = gem {
+        html.push_str(&format!("<p class=\"gem\">{}</p>\n", escape_html(gem)));
+    }
+    for paragraph in story.split('\n').filter(|line| !line.trim().is_empty()) {
+        html.push_str(&format!("<p>{}</p>\n", escape_html(paragraph)));
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() * 3);
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
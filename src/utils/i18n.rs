@@ -0,0 +1,75 @@
+use crate::models::Locale;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+const CATALOG_SOURCES: [(Locale, &str); 2] = [
+    (Locale::ZhCn, include_str!("../locales/zh-CN.toml")),
+    (Locale::En, include_str!("../locales/en.toml")),
+];
+
+type Catalog = HashMap<String, String>;
+
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        CATALOG_SOURCES
+            .iter()
+            .map(|(locale, source)| {
+                let table: toml::Table = toml::from_str(source).expect("UI catalog is not valid TOML");
+                let mut catalog = Catalog::new();
+                flatten("", &table, &mut catalog);
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+// `[control] title = ...` becomes the key `control.title`
+fn flatten(prefix: &str, table: &toml::Table, catalog: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::Table(nested) => flatten(&key, nested, catalog),
+            toml::Value::String(text) => {
+                catalog.insert(key, text.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct I18n;
+
+impl I18n {
+    // Missing keys fall back to the default locale, then to the key itself
+    pub fn t(locale: Locale, key: &'static str) -> &'static str {
+        Self::lookup(locale, key).unwrap_or(key)
+    }
+
+    // For keys built at runtime; `None` when neither catalog has the key
+    pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+        let catalogs = catalogs();
+        [locale, Locale::default()]
+            .iter()
+            .find_map(|locale| catalogs.get(locale)?.get(key))
+            .map(|text| text.as_str())
+    }
+
+    pub fn format(locale: Locale, key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = Self::t(locale, key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+
+    pub fn keys(locale: Locale) -> Vec<&'static str> {
+        let mut keys: Vec<&'static str> = catalogs()
+            .get(&locale)
+            .map(|catalog| catalog.keys().map(|key| key.as_str()).collect())
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+}
@@ -0,0 +1,40 @@
+use crate::models::ChoiceType;
+
+// Picks up a time gem in the terminal client; option keys are uppercase letters
+// or digits, so a story can never claim it
+pub const GEM_KEY: char = '+';
+
+// Keys the game responds to anywhere on the page; only Esc also works inside text fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    Choose(ChoiceType),
+    SkipText,
+    FocusMenu,
+}
+
+impl Shortcut {
+    // `key` is a KeyboardEvent.key value; option keys work in either case
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            " " | "Spacebar" => Some(Shortcut::SkipText),
+            "Escape" | "Esc" => Some(Shortcut::FocusMenu),
+            _ => {
+                let mut chars = key.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                ChoiceType::from_char(c.to_ascii_uppercase()).map(Shortcut::Choose)
+            }
+        }
+    }
+
+    // Value for `aria-keyshortcuts`, so assistive tech can announce the key
+    pub fn aria_keys(self) -> String {
+        match self {
+            Shortcut::Choose(choice_type) => choice_type.as_char().to_string(),
+            Shortcut::SkipText => "Space".to_string(),
+            Shortcut::FocusMenu => "Escape".to_string(),
+        }
+    }
+}
@@ -1,6 +1,16 @@
 // Utility modules for the L3 story game
 pub mod text_streaming;
 pub mod ascii_tree;
+pub mod cli_args;
+pub mod history_export;
+pub mod i18n;
+pub mod keyboard;
+pub mod tree_layout;
 
 pub use text_streaming::*;
-pub use ascii_tree::*;
\ No newline at end of file
+pub use ascii_tree::*;
+pub use cli_args::*;
+pub use history_export::*;
+pub use i18n::*;
+pub use keyboard::*;
+pub use tree_layout::*;
\ No newline at end of file
@@ -1,28 +1,39 @@
+use crate::models::TextSpeed;
 use std::time::Duration;
 
 pub struct TextStreamer {
     pub delay_ms: u64,
 }
 
+impl Default for TextStreamer {
+    fn default() -> Self {
+        Self::from_speed(TextSpeed::default())
+    }
+}
+
 impl TextStreamer {
     pub fn new(delay_ms: u64) -> Self {
         Self { delay_ms }
     }
     
-    pub fn default() -> Self {
-        Self::new(50)
+    pub fn from_speed(speed: TextSpeed) -> Self {
+        Self::new(speed.delay_ms())
     }
     
     pub fn smart_chunks(&self, text: &str) -> Vec<String> {
         // Split text into smart chunks for streaming
         let mut chunks = Vec::new();
         let mut current_chunk = String::new();
+        let mut chars = text.chars().peekable();
         
-        for char in text.chars() {
+        while let Some(char) = chars.next() {
             current_chunk.push(char);
             
-            // Break at natural pause points
-            if matches!(char, '。' | '！' | '？' | '；' | '：' | '，' | '\n') {
+            // Break at natural pause points; ASCII punctuation only counts before a space,
+            // so "3.0" and "2042," inside a clause stay whole
+            let ascii_pause = matches!(char, '.' | '!' | '?' | ';' | ':' | ',')
+                && chars.peek().is_none_or(|next| next.is_whitespace());
+            if ascii_pause || matches!(char, '。' | '！' | '？' | '；' | '：' | '，' | '\n') {
                 chunks.push(current_chunk.clone());
                 current_chunk.clear();
             }
@@ -39,4 +50,9 @@ impl TextStreamer {
     pub fn get_delay(&self) -> Duration {
         Duration::from_millis(self.delay_ms)
     }
-}
\ No newline at end of file
+    
+    // How long a chunk stays the newest one before the next appears
+    pub fn chunk_delay(&self, chunk: &str) -> Duration {
+        self.get_delay() * chunk.chars().count() as u32
+    }
+}
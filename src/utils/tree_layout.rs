@@ -0,0 +1,247 @@
+use crate::models::StoryGraph;
+use crate::utils::{escape_html, TreeOptions};
+use serde::{Deserialize, Serialize};
+
+pub const NODE_RADIUS: f64 = 14.0;
+const X_SPACING: f64 = 40.0;
+const Y_SPACING: f64 = 56.0;
+// Leaves room below the last row for the fold mark
+const MARGIN: f64 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeState {
+    Visited,
+    Current,
+    Unexplored,
+    Missing,
+}
+
+impl NodeState {
+    pub fn class(self) -> &'static str {
+        match self {
+            NodeState::Visited => "visited",
+            NodeState::Current => "current",
+            NodeState::Unexplored => "unexplored",
+            NodeState::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutNode {
+    pub path: String,
+    pub label: String,
+    pub title: String,
+    pub color: String,
+    pub state: NodeState,
+    // Has choices that the layout left folded
+    pub folded: bool,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl LayoutNode {
+    // Inline paint, so the SVG looks the same without a stylesheet
+    pub fn fill(&self) -> &str {
+        match self.state {
+            NodeState::Visited | NodeState::Current => self.color_or_default(),
+            NodeState::Unexplored | NodeState::Missing => "#ffffff",
+        }
+    }
+
+    pub fn stroke(&self) -> &str {
+        match self.state {
+            NodeState::Current => "#f08c00",
+            NodeState::Missing => "#adb5bd",
+            _ => self.color_or_default(),
+        }
+    }
+
+    pub fn text_color(&self) -> &str {
+        match self.state {
+            NodeState::Visited | NodeState::Current => "#ffffff",
+            NodeState::Unexplored | NodeState::Missing => "#212529",
+        }
+    }
+
+    pub fn stroke_width(&self) -> f64 {
+        if self.state == NodeState::Current { 4.0 } else { 2.0 }
+    }
+
+    pub fn dashed(&self) -> bool {
+        matches!(self.state, NodeState::Unexplored | NodeState::Missing)
+    }
+
+    fn color_or_default(&self) -> &str {
+        if self.color.is_empty() { "#495057" } else { &self.color }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LayoutEdge {
+    pub from: usize,
+    pub to: usize,
+    pub on_path: bool,
+}
+
+// Positions for the story tree, top-down: each level is a row and every leaf
+// gets its own column, so the browser and the exporter draw the same picture
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TreeLayout {
+    pub nodes: Vec<LayoutNode>,
+    pub edges: Vec<LayoutEdge>,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl TreeLayout {
+    // Folds and cuts off branches by the same rules as the text tree
+    pub fn build(graph: &StoryGraph, current_path: &str, options: TreeOptions) -> Self {
+        let mut layout = Self::default();
+        let Some(root) = graph.node(&graph.start) else {
+            return layout;
+        };
+
+        let mut columns = 0;
+        layout.nodes.push(LayoutNode {
+            path: String::new(),
+            label: "📚".to_string(),
+            title: root.content.title.clone(),
+            color: String::new(),
+            state: Self::state(current_path, ""),
+            folded: options.depth == 0 && !root.is_ending(),
+            x: 0.0,
+            y: 0.0,
+        });
+        if options.depth > 0 {
+            layout.place_children(graph, &graph.start, 0, current_path, options, &mut columns);
+        }
+        if layout.nodes.len() == 1 {
+            columns = 1;
+        }
+        let rows = layout.nodes.iter().map(|node| node.y).fold(0.0, f64::max);
+
+        for node in &mut layout.nodes {
+            node.x = MARGIN + node.x * X_SPACING;
+            node.y = MARGIN + node.y * Y_SPACING;
+        }
+        layout.width = 2.0 * MARGIN + (columns.max(1) - 1) as f64 * X_SPACING;
+        layout.height = 2.0 * MARGIN + rows * Y_SPACING;
+        layout
+    }
+
+    fn state(current_path: &str, path: &str) -> NodeState {
+        if path == current_path {
+            NodeState::Current
+        } else if current_path.starts_with(path) {
+            NodeState::Visited
+        } else {
+            NodeState::Unexplored
+        }
+    }
+
+    // Lays out the children of `parent` and centers it above them; x and y are
+    // in columns and rows until `build` scales them
+    fn place_children(&mut self, graph: &StoryGraph, id: &str, parent: usize, current_path: &str, options: TreeOptions, columns: &mut usize) {
+        let choice_options = graph
+            .node(id)
+            .and_then(|node| node.choice.as_ref())
+            .map_or(&[][..], |choice| &choice.options[..]);
+        let level = self.nodes[parent].y + 1.0;
+        let mut children = Vec::new();
+
+        for option in choice_options {
+            let path = format!("{}{}", self.nodes[parent].path, option.key);
+            let target = option.target.as_deref().unwrap_or_default();
+            let index = self.nodes.len();
+            let node = graph.node(target);
+            let on_path = current_path.starts_with(&path);
+            let has_children = node.is_some_and(|node| !node.is_ending()) && (level as usize) < graph.max_depth;
+            let open = has_children && options.opens(level as usize, on_path);
+
+            self.nodes.push(LayoutNode {
+                label: option.key.to_string(),
+                title: node.map_or_else(|| target.to_string(), |node| node.content.title.clone()),
+                color: option.color.clone(),
+                state: if node.is_some() { Self::state(current_path, &path) } else { NodeState::Missing },
+                folded: has_children && !open,
+                x: 0.0,
+                y: level,
+                path,
+            });
+            self.edges.push(LayoutEdge { from: parent, to: index, on_path });
+            children.push(index);
+
+            if open {
+                self.place_children(graph, target, index, current_path, options, columns);
+            } else {
+                self.nodes[index].x = *columns as f64;
+                *columns += 1;
+            }
+        }
+
+        match (children.first(), children.last()) {
+            (Some(first), Some(last)) => self.nodes[parent].x = (self.nodes[*first].x + self.nodes[*last].x) / 2.0,
+            _ => {
+                self.nodes[parent].x = *columns as f64;
+                *columns += 1;
+            }
+        }
+    }
+
+    // A standalone SVG document of the same picture the sidebar draws
+    pub fn to_svg(&self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">\n",
+            w = self.width,
+            h = self.height
+        );
+
+        for edge in &self.edges {
+            let (from, to) = (&self.nodes[edge.from], &self.nodes[edge.to]);
+            svg.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                from.x,
+                from.y,
+                to.x,
+                to.y,
+                if edge.on_path { "#f08c00" } else { "#ced4da" },
+                if edge.on_path { 3 } else { 1 }
+            ));
+        }
+
+        for node in &self.nodes {
+            svg.push_str(&format!("  <g class=\"tree-node {}\">\n", node.state.class()));
+            svg.push_str(&format!("    <title>{}</title>\n", escape_html(&node.title)));
+            svg.push_str(&format!(
+                "    <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"{}/>\n",
+                node.x,
+                node.y,
+                NODE_RADIUS,
+                escape_html(node.fill()),
+                escape_html(node.stroke()),
+                node.stroke_width(),
+                if node.dashed() { " stroke-dasharray=\"4 3\"" } else { "" }
+            ));
+            svg.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" font-size=\"12\" fill=\"{}\">{}</text>\n",
+                node.x,
+                node.y,
+                node.text_color(),
+                escape_html(&node.label)
+            ));
+            if node.folded {
+                svg.push_str(&format!(
+                    "    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\">…</text>\n",
+                    node.x,
+                    node.y + NODE_RADIUS + 12.0
+                ));
+            }
+            svg.push_str("  </g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}